@@ -0,0 +1,207 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Clock, Date, MILLISECONDS_PER_MINUTE};
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+// the weekdays and the leap years repeat every 400 years
+const DAYS_PER_400_YEARS: i64 = 146_097;
+
+const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+const WEEKDAYS: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseCronError
+{
+    /// The expression has not five fields and is not a macro like `@daily`.
+    InvalidFormat,
+    /// A field is not a list of values, ranges and steps, like `1,5-10,*/15`.
+    InvalidField,
+    /// A value is out of the range of its field, like the minute `60`.
+    OutOfRange
+}
+
+impl fmt::Display for ParseCronError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self
+        {
+            ParseCronError::InvalidFormat => write!(f, "invalid cron expression"),
+            ParseCronError::InvalidField => write!(f, "invalid cron field"),
+            ParseCronError::OutOfRange => write!(f, "cron value out of range"),
+        }
+    }
+}
+
+impl Error for ParseCronError {}
+
+/// A cron expression, firing at the minutes matching its five fields: the minute, the hour,
+/// the day of the month, the month and the day of the week.
+///
+/// Like in cron, a day matches when both its day fields match, or when either does if neither
+/// is `*`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Cron
+{
+    // the bit sets of the values of each field
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool
+}
+
+// a value, or a name counted from `first`
+fn value(text: &str, names: &[&str], first: u32) -> Result<u32, ParseCronError>
+{
+    if !text.is_empty() && text.bytes().all(|b| b.is_ascii_digit())
+    {
+        return text.parse().map_err(|_| ParseCronError::OutOfRange);
+    }
+
+    names
+        .iter()
+        .position(|name| name.eq_ignore_ascii_case(text))
+        .map(|index| index as u32 + first)
+        .ok_or(ParseCronError::InvalidField)
+}
+
+// the bit set of a field with values from `min` to `max`
+fn field(text: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, ParseCronError>
+{
+    let mut set = 0;
+
+    for item in text.split(',')
+    {
+        let (range, step) = match item.split_once('/')
+        {
+            Some((range, step)) => (range, Some(value(step, &[], 0)?)),
+            None => (item, None),
+        };
+        let (first, last) = match range.split_once('-')
+        {
+            _ if range == "*" => (min, max),
+            Some((first, last)) => (value(first, names, min)?, value(last, names, min)?),
+            // a single value with a step starts a range to the end of the field
+            None => {
+                let first = value(range, names, min)?;
+                (first, if step.is_some() {max} else {first})
+            }
+        };
+
+        if step == Some(0) || first > last
+        {
+            return Err(ParseCronError::InvalidField);
+        }
+        if first < min || last > max
+        {
+            return Err(ParseCronError::OutOfRange);
+        }
+        for bit in (first..=last).step_by(step.unwrap_or(1) as usize)
+        {
+            set |= 1 << bit;
+        }
+    }
+
+    Ok(set)
+}
+
+impl Cron
+{
+    fn matches_day(&self, date: Date) -> bool
+    {
+        let day = self.days & 1 << date.day() != 0;
+        let weekday = self.weekdays & 1 << date.weekday().number() != 0;
+
+        self.months & 1 << date.month() != 0
+            && match self.any_day || self.any_weekday
+            {
+                true => day && weekday,
+                false => day || weekday,
+            }
+    }
+
+    fn matches_minute(&self, minute: i64) -> bool
+    {
+        self.hours & 1 << (minute / 60) != 0 && self.minutes & 1 << (minute % 60) != 0
+    }
+
+    /// Whether the expression fires at a time, which has to be on a whole minute.
+    pub fn matches(&self, date: Date, clock: &Clock) -> bool
+    {
+        let milliseconds = clock.milliseconds_since_midnight();
+
+        milliseconds % MILLISECONDS_PER_MINUTE == 0
+            && self.matches_day(date)
+            && self.matches_minute(milliseconds / MILLISECONDS_PER_MINUTE)
+    }
+
+    /// The first time at or after a time when the expression fires, or `None` if it never
+    /// does, like on the 30th of February.
+    pub fn next_from(&self, date: Date, clock: &Clock) -> Option<(Date, Clock)>
+    {
+        // the first whole minute from the clock
+        let mut first = (clock.milliseconds_since_midnight() + MILLISECONDS_PER_MINUTE - 1) / MILLISECONDS_PER_MINUTE;
+
+        for days in 0..=DAYS_PER_400_YEARS
+        {
+            let day = date.add_days(days);
+            if self.matches_day(day)
+            {
+                if let Some(minute) = (first..MINUTES_PER_DAY).find(|&minute| self.matches_minute(minute))
+                {
+                    return Some((day, Clock::new(0, minute as i32)));
+                }
+            }
+            first = 0;
+        }
+
+        None
+    }
+}
+
+/// Parses the five fields separated by spaces, each a list of values, of ranges like `1-5`,
+/// and of `*`, where ranges and `*` may have a step like `*/15`. The months and the days of
+/// the week may be given by the first three letters of their English names, and Sunday is
+/// either `0` or `7`. The macros `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly`
+/// are accepted too.
+impl FromStr for Cron
+{
+    type Err = ParseCronError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let s = match s.trim()
+        {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            s => s,
+        };
+
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        if fields.len() != 5
+        {
+            return Err(ParseCronError::InvalidFormat);
+        }
+
+        let weekdays = field(fields[4], 0, 7, &WEEKDAYS)?;
+
+        Ok(Cron
+        {
+            minutes: field(fields[0], 0, 59, &[])?,
+            hours: field(fields[1], 0, 23, &[])?,
+            days: field(fields[2], 1, 31, &[])?,
+            months: field(fields[3], 1, 12, &MONTHS)?,
+            // Sunday is both 0 and 7
+            weekdays: (weekdays | weekdays >> 7) & 0x7f,
+            any_day: fields[2].starts_with('*'),
+            any_weekday: fields[4].starts_with('*')
+        })
+    }
+}
@@ -0,0 +1,143 @@
+use std::fmt;
+
+/// A day of the week, numbered from 0 for Sunday like in cron and in the POSIX time zones.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Weekday
+{
+    Sunday,
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday
+}
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Sunday,
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday
+];
+
+impl Weekday
+{
+    pub fn from_number(number: u32) -> Option<Weekday>
+    {
+        WEEKDAYS.get(number as usize).copied()
+    }
+
+    pub fn number(&self) -> u32
+    {
+        *self as u32
+    }
+}
+
+/// A day of the proleptic Gregorian calendar.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Date
+{
+    year: i32,
+    month: u32,
+    day: u32
+}
+
+pub(crate) fn is_leap_year(year: i32) -> bool
+{
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+pub(crate) fn days_in_month(year: i32, month: u32) -> u32
+{
+    match month
+    {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date
+{
+    /// Returns `None` if the month or the day does not exist.
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Date>
+    {
+        match (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month)
+        {
+            true => Some(Date { year, month, day }),
+            false => None,
+        }
+    }
+
+    /// The date a number of days after 1970-01-01.
+    pub fn from_days_since_epoch(days: i64) -> Date
+    {
+        // the years are counted from March, so that the leap day ends them
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {shifted_month + 3} else {shifted_month - 9};
+        let year = year_of_era + era * 400 + if month <= 2 {1} else {0};
+
+        Date
+        {
+            year: year as i32,
+            month: month as u32,
+            day: day as u32
+        }
+    }
+
+    pub fn days_since_epoch(&self) -> i64
+    {
+        let year = self.year as i64 - if self.month <= 2 {1} else {0};
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let shifted_month = (self.month as i64 + 9) % 12;
+        let day_of_year = (153 * shifted_month + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    pub fn year(&self) -> i32
+    {
+        self.year
+    }
+
+    pub fn month(&self) -> u32
+    {
+        self.month
+    }
+
+    pub fn day(&self) -> u32
+    {
+        self.day
+    }
+
+    pub fn weekday(&self) -> Weekday
+    {
+        // 1970-01-01 was a Thursday
+        WEEKDAYS[(self.days_since_epoch() + 4).rem_euclid(7) as usize]
+    }
+
+    pub fn add_days(&self, days: i64) -> Date
+    {
+        Date::from_days_since_epoch(self.days_since_epoch() + days)
+    }
+}
+
+/// Prints `YYYY-MM-DD`.
+impl fmt::Display for Date
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
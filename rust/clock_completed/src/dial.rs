@@ -0,0 +1,139 @@
+use crate::{Clock, Duration, MILLISECONDS_PER_DAY, MILLISECONDS_PER_HOUR, MILLISECONDS_PER_SECOND};
+
+/// How a clock counts: the hours in a turn of the dial, the minutes in an hour, the seconds in a
+/// minute, and how long a turn lasts in real time.
+///
+/// A second always has 1000 milliseconds, which are not real ones when a turn does not last as
+/// long as its milliseconds, like on the decimal dial.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Dial
+{
+    hours: i32,
+    minutes: i32,
+    seconds: i32,
+    period: i64
+}
+
+impl Dial
+{
+    pub const TWENTY_FOUR_HOUR: Dial = Dial { hours: 24, minutes: 60, seconds: 60, period: MILLISECONDS_PER_DAY };
+    pub const TWELVE_HOUR: Dial = Dial { hours: 12, minutes: 60, seconds: 60, period: 12 * MILLISECONDS_PER_HOUR };
+    /// The French revolutionary time: a day of 10 hours of 100 minutes of 100 seconds.
+    pub const DECIMAL: Dial = Dial { hours: 10, minutes: 100, seconds: 100, period: MILLISECONDS_PER_DAY };
+
+    /// Returns `None` unless every amount and the period are positive.
+    pub fn new(hours: i32, minutes: i32, seconds: i32, period: Duration) -> Option<Dial>
+    {
+        let period = period.as_milliseconds();
+
+        match hours > 0 && minutes > 0 && seconds > 0 && period > 0
+        {
+            true => Some(Dial { hours, minutes, seconds, period }),
+            false => None,
+        }
+    }
+
+    /// A shift clock turning once every amount of real hours.
+    pub fn shift(hours: i32) -> Option<Dial>
+    {
+        Dial::new(hours, 60, 60, Duration::from_hours(hours as i64))
+    }
+
+    pub fn hours(&self) -> i32
+    {
+        self.hours
+    }
+
+    pub fn minutes(&self) -> i32
+    {
+        self.minutes
+    }
+
+    pub fn seconds(&self) -> i32
+    {
+        self.seconds
+    }
+
+    /// The real time a turn of the dial lasts.
+    pub fn period(&self) -> Duration
+    {
+        Duration::from_milliseconds(self.period)
+    }
+
+    // the milliseconds of the dial in a turn
+    fn turn(&self) -> i128
+    {
+        self.hours as i128 * self.minutes as i128 * self.seconds as i128 * MILLISECONDS_PER_SECOND as i128
+    }
+
+    pub fn clock(&self, hours: i32, minutes: i32) -> Clock
+    {
+        self.clock_with_milliseconds(hours, minutes, 0, 0)
+    }
+
+    pub fn clock_with_seconds(&self, hours: i32, minutes: i32, seconds: i32) -> Clock
+    {
+        self.clock_with_milliseconds(hours, minutes, seconds, 0)
+    }
+
+    /// Every unit may be out of its range or negative, and carries over to the next one,
+    /// the hours wrapping around the dial.
+    pub fn clock_with_milliseconds(&self, hours: i32, minutes: i32, seconds: i32, milliseconds: i32) -> Clock
+    {
+        let minute = self.seconds as i128 * MILLISECONDS_PER_SECOND as i128;
+        let hour = self.minutes as i128 * minute;
+
+        self.clock_at(
+            hours as i128 * hour
+                + minutes as i128 * minute
+                + seconds as i128 * MILLISECONDS_PER_SECOND as i128
+                + milliseconds as i128
+        )
+    }
+
+    // the clock an amount of milliseconds of the dial after its top, wrapping around it
+    fn clock_at(&self, milliseconds: i128) -> Clock
+    {
+        let milliseconds = milliseconds.rem_euclid(self.turn());
+        let second = MILLISECONDS_PER_SECOND as i128;
+        let minute = self.seconds as i128 * second;
+        let hour = self.minutes as i128 * minute;
+
+        Clock
+        {
+            hours: (milliseconds / hour) as i32,
+            minutes: (milliseconds % hour / minute) as i32,
+            seconds: (milliseconds % minute / second) as i32,
+            milliseconds: (milliseconds % second) as i32,
+            dial: *self
+        }
+    }
+
+    // the clock an amount of real milliseconds after the top of the dial, rounded down to a
+    // millisecond of the dial
+    pub(crate) fn clock_after(&self, milliseconds: i64) -> Clock
+    {
+        let milliseconds = (milliseconds as i128).rem_euclid(self.period as i128);
+        self.clock_at(milliseconds * self.turn() / self.period as i128)
+    }
+
+    // the real milliseconds from the top of the dial to a clock on it
+    pub(crate) fn milliseconds_to(&self, clock: &Clock) -> i64
+    {
+        let milliseconds = ((clock.hours as i128 * self.minutes as i128 + clock.minutes as i128) * self.seconds as i128
+            + clock.seconds as i128)
+            * MILLISECONDS_PER_SECOND as i128
+            + clock.milliseconds as i128;
+
+        (milliseconds * self.period as i128 / self.turn()) as i64
+    }
+}
+
+/// The 24-hour dial.
+impl Default for Dial
+{
+    fn default() -> Self
+    {
+        Dial::TWENTY_FOUR_HOUR
+    }
+}
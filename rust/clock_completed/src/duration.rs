@@ -0,0 +1,80 @@
+use std::ops::{Add, Neg, Sub};
+
+use crate::{MILLISECONDS_PER_HOUR, MILLISECONDS_PER_MINUTE, MILLISECONDS_PER_SECOND};
+
+/// A signed amount of time, with millisecond resolution.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub struct Duration
+{
+    milliseconds: i64
+}
+
+impl Duration
+{
+    pub fn from_hours(hours: i64) -> Self
+    {
+        Duration::from_milliseconds(hours * MILLISECONDS_PER_HOUR)
+    }
+
+    pub fn from_minutes(minutes: i64) -> Self
+    {
+        Duration::from_milliseconds(minutes * MILLISECONDS_PER_MINUTE)
+    }
+
+    pub fn from_seconds(seconds: i64) -> Self
+    {
+        Duration::from_milliseconds(seconds * MILLISECONDS_PER_SECOND)
+    }
+
+    pub fn from_milliseconds(milliseconds: i64) -> Self
+    {
+        Duration { milliseconds }
+    }
+
+    /// The whole minutes, rounded toward zero.
+    pub fn as_minutes(&self) -> i64
+    {
+        self.milliseconds / MILLISECONDS_PER_MINUTE
+    }
+
+    /// The whole seconds, rounded toward zero.
+    pub fn as_seconds(&self) -> i64
+    {
+        self.milliseconds / MILLISECONDS_PER_SECOND
+    }
+
+    pub fn as_milliseconds(&self) -> i64
+    {
+        self.milliseconds
+    }
+}
+
+impl Add for Duration
+{
+    type Output = Duration;
+
+    fn add(self, other: Duration) -> Duration
+    {
+        Duration::from_milliseconds(self.milliseconds + other.milliseconds)
+    }
+}
+
+impl Sub for Duration
+{
+    type Output = Duration;
+
+    fn sub(self, other: Duration) -> Duration
+    {
+        Duration::from_milliseconds(self.milliseconds - other.milliseconds)
+    }
+}
+
+impl Neg for Duration
+{
+    type Output = Duration;
+
+    fn neg(self) -> Duration
+    {
+        Duration::from_milliseconds(-self.milliseconds)
+    }
+}
@@ -1,36 +1,255 @@
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::Formatter;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Sub, SubAssign};
 
-#[derive(PartialEq, Debug)]
+mod cron;
+mod date;
+mod dial;
+mod duration;
+mod parse;
+mod schedule;
+#[cfg(feature = "serde")]
+mod serialize;
+mod zone;
+
+pub use cron::{Cron, ParseCronError};
+pub use date::{Date, Weekday};
+pub use dial::Dial;
+pub use duration::Duration;
+pub use parse::ParseClockError;
+pub use schedule::{overlapping, Alarm, Firing, Schedule, TimeRange, Trigger, Upcoming, Weekdays};
+#[cfg(feature = "serde")]
+pub use serialize::minutes_since_midnight;
+pub use zone::{LocalResult, Zone, ZoneError, ZonedClock};
+
+const MILLISECONDS_PER_SECOND: i64 = 1000;
+const MILLISECONDS_PER_MINUTE: i64 = 60 * MILLISECONDS_PER_SECOND;
+const MILLISECONDS_PER_HOUR: i64 = 60 * MILLISECONDS_PER_MINUTE;
+const MILLISECONDS_PER_DAY: i64 = 24 * MILLISECONDS_PER_HOUR;
+
+/// A time of the day, ordered from midnight, and then by dial: the same time on different dials
+/// is not equal.
+#[derive(Clone, Copy, Debug)]
 pub struct Clock
 {
     hours: i32,
-    minutes: i32
+    minutes: i32,
+    seconds: i32,
+    milliseconds: i32,
+    dial: Dial
 }
 
 impl Clock
 {
     pub fn new(hours: i32, minutes: i32) -> Self
     {
-        let right_hours = hours.rem_euclid(24) + minutes / 60 - (if minutes < 0 && minutes.rem_euclid(60) != 0 {1} else {0});
-        let right_minutes = minutes.rem_euclid(60);
+        Clock::with_milliseconds(hours, minutes, 0, 0)
+    }
+
+    pub fn with_seconds(hours: i32, minutes: i32, seconds: i32) -> Self
+    {
+        Clock::with_milliseconds(hours, minutes, seconds, 0)
+    }
+
+    /// Every unit may be out of its range or negative, and carries over to the next one,
+    /// the hours wrapping around the day.
+    pub fn with_milliseconds(hours: i32, minutes: i32, seconds: i32, milliseconds: i32) -> Self
+    {
+        Dial::default().clock_with_milliseconds(hours, minutes, seconds, milliseconds)
+    }
 
-        Clock
+    // the clock an amount of milliseconds after midnight, wrapping around the day
+    fn from_milliseconds(milliseconds: i64) -> Self
+    {
+        Dial::default().clock_after(milliseconds)
+    }
+
+    // the real milliseconds since the top of the dial, which is midnight on the 24-hour dial
+    fn milliseconds_since_midnight(&self) -> i64
+    {
+        self.dial.milliseconds_to(self)
+    }
+
+    // what clocks are compared by, the fields only telling apart the times of a dial which fall
+    // on the same millisecond
+    fn key(&self) -> (i64, Dial, i32, i32, i32, i32)
+    {
+        (self.milliseconds_since_midnight(), self.dial, self.hours, self.minutes, self.seconds, self.milliseconds)
+    }
+
+    pub fn hours(&self) -> i32
+    {
+        self.hours
+    }
+
+    pub fn minutes(&self) -> i32
+    {
+        self.minutes
+    }
+
+    pub fn seconds(&self) -> i32
+    {
+        self.seconds
+    }
+
+    pub fn milliseconds(&self) -> i32
+    {
+        self.milliseconds
+    }
+
+    pub fn dial(&self) -> Dial
+    {
+        self.dial
+    }
+
+    /// The same time read on another dial, rounded down to a millisecond of that dial.
+    pub fn to_dial(&self, dial: Dial) -> Self
+    {
+        dial.clock_after(self.milliseconds_since_midnight())
+    }
+
+    /// The minutes since midnight on the 24-hour dial, from 0 to 1439, the seconds being dropped.
+    pub fn to_packed(&self) -> u16
+    {
+        (self.to_dial(Dial::default()).milliseconds_since_midnight() / MILLISECONDS_PER_MINUTE) as u16
+    }
+
+    /// Returns `None` if there are 1440 minutes or more.
+    pub fn from_packed(packed: u16) -> Option<Self>
+    {
+        match (packed as i64) < MILLISECONDS_PER_DAY / MILLISECONDS_PER_MINUTE
         {
-            hours: right_hours.rem_euclid(24),
-            minutes: right_minutes
+            true => Some(Clock::new(0, packed as i32)),
+            false => None,
         }
     }
 
+    /// Adds minutes of the clock's dial.
     pub fn add_minutes(&self, minutes: i32) -> Self
     {
-        Clock::new(self.hours, self.minutes + minutes)
+        self.dial.clock_with_milliseconds(self.hours, self.minutes + minutes, self.seconds, self.milliseconds)
+    }
+
+    /// Adds seconds of the clock's dial.
+    pub fn add_seconds(&self, seconds: i32) -> Self
+    {
+        self.dial.clock_with_milliseconds(self.hours, self.minutes, self.seconds + seconds, self.milliseconds)
+    }
+
+    /// Adds milliseconds of the clock's dial.
+    pub fn add_milliseconds(&self, milliseconds: i32) -> Self
+    {
+        self.dial.clock_with_milliseconds(self.hours, self.minutes, self.seconds, self.milliseconds + milliseconds)
     }
 }
 
+/// Prints `HH:MM`, followed by `:SS` when there are seconds and by `.mmm` when there are
+/// milliseconds.
 impl fmt::Display for Clock
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{:02}:{:02}", self.hours, self.minutes)
+        write!(f, "{:02}:{:02}", self.hours, self.minutes)?;
+
+        if self.seconds != 0 || self.milliseconds != 0
+        {
+            write!(f, ":{:02}", self.seconds)?;
+        }
+        if self.milliseconds != 0
+        {
+            write!(f, ".{:03}", self.milliseconds)?;
+        }
+
+        Ok(())
     }
-}
\ No newline at end of file
+}
+
+impl PartialEq for Clock
+{
+    fn eq(&self, other: &Clock) -> bool
+    {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Clock {}
+
+impl PartialOrd for Clock
+{
+    fn partial_cmp(&self, other: &Clock) -> Option<Ordering>
+    {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Clock
+{
+    fn cmp(&self, other: &Clock) -> Ordering
+    {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for Clock
+{
+    fn hash<H: Hasher>(&self, state: &mut H)
+    {
+        self.key().hash(state);
+    }
+}
+
+impl Add<Duration> for Clock
+{
+    type Output = Clock;
+
+    fn add(self, duration: Duration) -> Clock
+    {
+        self.dial.clock_after(self.milliseconds_since_midnight() + duration.as_milliseconds())
+    }
+}
+
+impl Sub<Duration> for Clock
+{
+    type Output = Clock;
+
+    fn sub(self, duration: Duration) -> Clock
+    {
+        self.dial.clock_after(self.milliseconds_since_midnight() - duration.as_milliseconds())
+    }
+}
+
+impl AddAssign<Duration> for Clock
+{
+    fn add_assign(&mut self, duration: Duration)
+    {
+        *self = self.dial.clock_after(self.milliseconds_since_midnight() + duration.as_milliseconds());
+    }
+}
+
+impl SubAssign<Duration> for Clock
+{
+    fn sub_assign(&mut self, duration: Duration)
+    {
+        *self = self.dial.clock_after(self.milliseconds_since_midnight() - duration.as_milliseconds());
+    }
+}
+
+/// The shortest way around the dial of `self` from `other` to `self`, in real time: at most
+/// half a turn forward or less than half a turn backward, 12 hours on the 24-hour dial.
+impl Sub for Clock
+{
+    type Output = Duration;
+
+    fn sub(self, other: Clock) -> Duration
+    {
+        let period = self.dial.period().as_milliseconds();
+        let difference = (self.milliseconds_since_midnight() - other.milliseconds_since_midnight()).rem_euclid(period);
+
+        match difference > period / 2
+        {
+            true => Duration::from_milliseconds(difference - period),
+            false => Duration::from_milliseconds(difference),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::Clock;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ParseClockError
+{
+    /// The text is not like `HH:MM`, `HH:MM:SS`, `HH:MM:SS.mmm` or `h:mm am`.
+    InvalidFormat,
+    /// A field is out of its range, like in `24:00` or `13:00 pm`.
+    OutOfRange
+}
+
+impl fmt::Display for ParseClockError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self
+        {
+            ParseClockError::InvalidFormat => write!(f, "invalid time format"),
+            ParseClockError::OutOfRange => write!(f, "time field out of range"),
+        }
+    }
+}
+
+impl Error for ParseClockError {}
+
+// a field made of an amount of digits in the range
+fn number(field: Option<&str>, digits: RangeInclusive<usize>) -> Result<i32, ParseClockError>
+{
+    match field
+    {
+        Some(field) if digits.contains(&field.len()) && field.bytes().all(|b| b.is_ascii_digit()) => {
+            Ok(field.parse().unwrap())
+        }
+        _ => Err(ParseClockError::InvalidFormat),
+    }
+}
+
+/// Parses the 24-hour `HH:MM`, `HH:MM:SS` and `HH:MM:SS.mmm`, where the hours may have a
+/// single digit, and the same followed by `am` or `pm` for the 12-hour dial, like `7:05 pm`.
+impl FromStr for Clock
+{
+    type Err = ParseClockError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let s = s.trim().to_ascii_lowercase();
+        let (time, pm) = match (s.strip_suffix("am"), s.strip_suffix("pm"))
+        {
+            (Some(time), _) => (time.trim_end(), Some(false)),
+            (_, Some(time)) => (time.trim_end(), Some(true)),
+            _ => (s.as_str(), None),
+        };
+
+        let mut fields = time.split(':');
+        let hours = number(fields.next(), 1..=2)?;
+        let minutes = number(fields.next(), 2..=2)?;
+        let (seconds, milliseconds) = match fields.next()
+        {
+            None => (0, 0),
+            Some(field) => match field.split_once('.')
+            {
+                None => (number(Some(field), 2..=2)?, 0),
+                Some((seconds, milliseconds)) => (number(Some(seconds), 2..=2)?, number(Some(milliseconds), 3..=3)?),
+            },
+        };
+        if fields.next().is_some()
+        {
+            return Err(ParseClockError::InvalidFormat);
+        }
+
+        // 12 am is midnight, and 12 pm is noon
+        let hours = match pm
+        {
+            None if hours < 24 => hours,
+            Some(pm) if (1..=12).contains(&hours) => hours % 12 + if pm {12} else {0},
+            _ => return Err(ParseClockError::OutOfRange),
+        };
+        if minutes > 59 || seconds > 59
+        {
+            return Err(ParseClockError::OutOfRange);
+        }
+
+        Ok(Clock::with_milliseconds(hours, minutes, seconds, milliseconds))
+    }
+}
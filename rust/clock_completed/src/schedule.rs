@@ -0,0 +1,309 @@
+use std::str::FromStr;
+
+use crate::{Clock, Cron, Date, Duration, ParseClockError, Weekday, MILLISECONDS_PER_DAY};
+
+/// A set of days of the week.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Weekdays
+{
+    // a bit for each day, from Sunday
+    mask: u8
+}
+
+impl Weekdays
+{
+    pub const NONE: Weekdays = Weekdays { mask: 0 };
+    pub const EVERY_DAY: Weekdays = Weekdays { mask: 0b111_1111 };
+    /// From Monday to Friday.
+    pub const WORKDAYS: Weekdays = Weekdays { mask: 0b011_1110 };
+    pub const WEEKEND: Weekdays = Weekdays { mask: 0b100_0001 };
+
+    pub fn from_days(days: &[Weekday]) -> Self
+    {
+        days.iter().fold(Weekdays::NONE, |weekdays, &day| weekdays.with(day))
+    }
+
+    pub fn with(&self, day: Weekday) -> Self
+    {
+        Weekdays { mask: self.mask | 1 << day.number() }
+    }
+
+    pub fn without(&self, day: Weekday) -> Self
+    {
+        Weekdays { mask: self.mask & !(1 << day.number()) }
+    }
+
+    pub fn contains(&self, day: Weekday) -> bool
+    {
+        self.mask & 1 << day.number() != 0
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.mask == 0
+    }
+}
+
+/// An alarm ringing at a time on some days of the week.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Alarm
+{
+    time: Clock,
+    weekdays: Weekdays
+}
+
+impl Alarm
+{
+    pub fn new(time: Clock, weekdays: Weekdays) -> Self
+    {
+        Alarm { time, weekdays }
+    }
+
+    pub fn daily(time: Clock) -> Self
+    {
+        Alarm::new(time, Weekdays::EVERY_DAY)
+    }
+
+    pub fn time(&self) -> &Clock
+    {
+        &self.time
+    }
+
+    pub fn weekdays(&self) -> Weekdays
+    {
+        self.weekdays
+    }
+
+    /// The first time at or after a time when the alarm rings, or `None` if it rings on no day.
+    pub fn next_from(&self, date: Date, clock: &Clock) -> Option<(Date, Clock)>
+    {
+        let first = if self.time.milliseconds_since_midnight() >= clock.milliseconds_since_midnight() {0} else {1};
+
+        (first..first + 7)
+            .map(|days| date.add_days(days))
+            .find(|day| self.weekdays.contains(day.weekday()))
+            .map(|day| (day, self.time))
+    }
+}
+
+/// The times of the day from `start` until `end`, wrapping past midnight when `end` is not after
+/// `start`. A range starting and ending at the same time spans the whole day.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TimeRange
+{
+    start: Clock,
+    end: Clock
+}
+
+impl TimeRange
+{
+    pub fn new(start: Clock, end: Clock) -> Self
+    {
+        TimeRange { start, end }
+    }
+
+    pub fn start(&self) -> &Clock
+    {
+        &self.start
+    }
+
+    pub fn end(&self) -> &Clock
+    {
+        &self.end
+    }
+
+    pub fn wraps_past_midnight(&self) -> bool
+    {
+        self.end.milliseconds_since_midnight() <= self.start.milliseconds_since_midnight()
+    }
+
+    pub fn duration(&self) -> Duration
+    {
+        let duration = (self.end.milliseconds_since_midnight() - self.start.milliseconds_since_midnight()).rem_euclid(MILLISECONDS_PER_DAY);
+        Duration::from_milliseconds(if duration == 0 {MILLISECONDS_PER_DAY} else {duration})
+    }
+
+    /// Whether the range contains a time, the end being excluded.
+    pub fn contains(&self, clock: &Clock) -> bool
+    {
+        let start = self.start.milliseconds_since_midnight();
+        let end = self.end.milliseconds_since_midnight();
+        let clock = clock.milliseconds_since_midnight();
+
+        match start < end
+        {
+            true => start <= clock && clock < end,
+            false => start <= clock || clock < end,
+        }
+    }
+
+    /// Whether the ranges have a time in common.
+    pub fn overlaps(&self, other: &TimeRange) -> bool
+    {
+        // the arcs of the dial overlap if one of them starts in the other
+        self.contains(&other.start) || other.contains(&self.start)
+    }
+}
+
+/// Parses two times separated by `-`, like `22:00-06:30`.
+impl FromStr for TimeRange
+{
+    type Err = ParseClockError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let (start, end) = s.split_once('-').ok_or(ParseClockError::InvalidFormat)?;
+        Ok(TimeRange::new(start.parse()?, end.parse()?))
+    }
+}
+
+/// The pairs of indexes of the ranges overlapping each other, in order.
+pub fn overlapping(ranges: &[TimeRange]) -> Vec<(usize, usize)>
+{
+    let mut pairs = vec![];
+    for (i, range) in ranges.iter().enumerate()
+    {
+        for (j, other) in ranges.iter().enumerate().skip(i + 1)
+        {
+            if range.overlaps(other)
+            {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Trigger
+{
+    Alarm(Alarm),
+    Cron(Cron)
+}
+
+impl Trigger
+{
+    pub fn next_from(&self, date: Date, clock: &Clock) -> Option<(Date, Clock)>
+    {
+        match self
+        {
+            Trigger::Alarm(alarm) => alarm.next_from(date, clock),
+            Trigger::Cron(cron) => cron.next_from(date, clock),
+        }
+    }
+}
+
+impl From<Alarm> for Trigger
+{
+    fn from(alarm: Alarm) -> Self
+    {
+        Trigger::Alarm(alarm)
+    }
+}
+
+impl From<Cron> for Trigger
+{
+    fn from(cron: Cron) -> Self
+    {
+        Trigger::Cron(cron)
+    }
+}
+
+/// A set of alarms and cron expressions.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Schedule
+{
+    triggers: Vec<Trigger>
+}
+
+/// A time when a trigger of a schedule fires.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Firing
+{
+    date: Date,
+    clock: Clock,
+    trigger: usize
+}
+
+impl Firing
+{
+    pub fn date(&self) -> Date
+    {
+        self.date
+    }
+
+    pub fn clock(&self) -> &Clock
+    {
+        &self.clock
+    }
+
+    /// The index of the trigger in the schedule.
+    pub fn trigger(&self) -> usize
+    {
+        self.trigger
+    }
+}
+
+impl Schedule
+{
+    pub fn new() -> Self
+    {
+        Schedule { triggers: vec![] }
+    }
+
+    /// Adds a trigger, and returns its index.
+    pub fn add(&mut self, trigger: impl Into<Trigger>) -> usize
+    {
+        self.triggers.push(trigger.into());
+        self.triggers.len() - 1
+    }
+
+    pub fn triggers(&self) -> &[Trigger]
+    {
+        &self.triggers
+    }
+
+    /// The firings at or after a time, in order, the ones at the same time in the order of
+    /// their triggers.
+    pub fn upcoming(&self, date: Date, clock: &Clock) -> Upcoming<'_>
+    {
+        Upcoming
+        {
+            triggers: &self.triggers,
+            next: self.triggers.iter().map(|trigger| trigger.next_from(date, clock)).collect()
+        }
+    }
+}
+
+pub struct Upcoming<'a>
+{
+    triggers: &'a [Trigger],
+    // the next firing of each trigger
+    next: Vec<Option<(Date, Clock)>>
+}
+
+impl Iterator for Upcoming<'_>
+{
+    type Item = Firing;
+
+    fn next(&mut self) -> Option<Firing>
+    {
+        let (trigger, (date, clock)) = self
+            .next
+            .iter()
+            .enumerate()
+            .filter_map(|(trigger, next)| next.as_ref().map(|next| (trigger, next)))
+            .min_by_key(|(_, (date, clock))| (*date, clock.milliseconds_since_midnight()))
+            .map(|(trigger, (date, clock))| (trigger, (*date, *clock)))?;
+
+        // the trigger fires next from a millisecond later
+        let (after_date, after) = match clock.milliseconds_since_midnight() + 1
+        {
+            MILLISECONDS_PER_DAY => (date.add_days(1), Clock::new(0, 0)),
+            milliseconds => (date, Clock::from_milliseconds(milliseconds)),
+        };
+        self.next[trigger] = self.triggers[trigger].next_from(after_date, &after);
+
+        Some(Firing { date, clock, trigger })
+    }
+}
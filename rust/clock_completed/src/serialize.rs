@@ -0,0 +1,110 @@
+use std::fmt;
+
+use serde::de::{self, Unexpected, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Clock, Dial};
+
+const MINUTES_PER_DAY: u64 = 24 * 60;
+
+struct ClockVisitor
+{
+    // whether a string like `HH:MM` is expected, or else minutes since midnight
+    text: bool
+}
+
+impl ClockVisitor
+{
+    fn minutes<E: de::Error>(&self, minutes: u64, unexpected: Unexpected<'_>) -> Result<Clock, E>
+    {
+        match minutes < MINUTES_PER_DAY
+        {
+            true => Ok(Clock::new(0, minutes as i32)),
+            false => Err(E::invalid_value(unexpected, self)),
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for ClockVisitor
+{
+    type Value = Clock;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.text
+        {
+            true => write!(f, "a time like \"HH:MM\""),
+            false => write!(f, "minutes since midnight, from 0 to 1439"),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Clock, E>
+    {
+        match self.text
+        {
+            true => v.parse().map_err(|_| E::invalid_value(Unexpected::Str(v), &self)),
+            false => Err(E::invalid_type(Unexpected::Str(v), &self)),
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Clock, E>
+    {
+        self.minutes(v, Unexpected::Unsigned(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Clock, E>
+    {
+        match u64::try_from(v)
+        {
+            Ok(minutes) => self.minutes(minutes, Unexpected::Signed(v)),
+            Err(_) => Err(E::invalid_value(Unexpected::Signed(v), &self)),
+        }
+    }
+}
+
+/// Serializes as text like `07:05` or `07:05:30.250`, read on the 24-hour dial, in human
+/// readable formats, and as its minutes since midnight like [`Clock::to_packed`] in the others.
+impl Serialize for Clock
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        match serializer.is_human_readable()
+        {
+            true => serializer.collect_str(&self.to_dial(Dial::default())),
+            false => serializer.serialize_u16(self.to_packed()),
+        }
+    }
+}
+
+/// Deserializes from text, in any form `Clock` parses, in human readable formats, and from
+/// minutes since midnight in the others.
+impl<'de> Deserialize<'de> for Clock
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>
+    {
+        match deserializer.is_human_readable()
+        {
+            true => deserializer.deserialize_str(ClockVisitor { text: true }),
+            false => deserializer.deserialize_u16(ClockVisitor { text: false }),
+        }
+    }
+}
+
+/// Serializes a clock as its minutes since midnight, dropping the seconds like
+/// [`Clock::to_packed`], for `#[serde(with = "clock::minutes_since_midnight")]`.
+pub mod minutes_since_midnight
+{
+    use serde::{Deserializer, Serializer};
+
+    use super::ClockVisitor;
+    use crate::Clock;
+
+    pub fn serialize<S: Serializer>(clock: &Clock, serializer: S) -> Result<S::Ok, S::Error>
+    {
+        serializer.serialize_u16(clock.to_packed())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Clock, D::Error>
+    {
+        deserializer.deserialize_u16(ClockVisitor { text: false })
+    }
+}
@@ -0,0 +1,656 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::date::days_in_month;
+use crate::{Clock, Date, Duration, MILLISECONDS_PER_DAY, MILLISECONDS_PER_SECOND};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+// the current rules of common zones, for when there is no tzdata to read them from
+const EMBEDDED: [(&str, &str); 16] = [
+    ("UTC", "UTC0"),
+    ("Europe/London", "GMT0BST,M3.5.0/1,M10.5.0"),
+    ("Europe/Paris", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Berlin", "CET-1CEST,M3.5.0,M10.5.0/3"),
+    ("Europe/Moscow", "MSK-3"),
+    ("America/New_York", "EST5EDT,M3.2.0,M11.1.0"),
+    ("America/Chicago", "CST6CDT,M3.2.0,M11.1.0"),
+    ("America/Denver", "MST7MDT,M3.2.0,M11.1.0"),
+    ("America/Phoenix", "MST7"),
+    ("America/Los_Angeles", "PST8PDT,M3.2.0,M11.1.0"),
+    ("America/Sao_Paulo", "<-03>3"),
+    ("Asia/Kolkata", "IST-5:30"),
+    ("Asia/Shanghai", "CST-8"),
+    ("Asia/Tokyo", "JST-9"),
+    ("Australia/Sydney", "AEST-10AEDT,M10.1.0,M4.1.0/3"),
+    ("Pacific/Auckland", "NZST-12NZDT,M9.5.0,M4.1.0/3")
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ZoneError
+{
+    /// There is no tzdata nor embedded rules for the zone name.
+    UnknownZone,
+    /// The tzdata file is not a valid TZif file.
+    InvalidData,
+    /// The POSIX TZ rule is not well formed.
+    InvalidRule
+}
+
+impl fmt::Display for ZoneError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self
+        {
+            ZoneError::UnknownZone => write!(f, "unknown time zone"),
+            ZoneError::InvalidData => write!(f, "invalid tzdata file"),
+            ZoneError::InvalidRule => write!(f, "invalid POSIX time zone rule"),
+        }
+    }
+}
+
+impl Error for ZoneError {}
+
+/// The wall times of a zone: most of them happen once, the ones in the hour repeated when
+/// the clocks go back happen twice, and the ones in the hour skipped when the clocks go
+/// forward never happen.
+#[derive(Clone, PartialEq, Debug)]
+pub enum LocalResult<T>
+{
+    Single(T),
+    /// The earlier one first.
+    Ambiguous(T, T),
+    /// Counted with the offset from before the clocks went forward, which moves the wall time
+    /// forward by the length of the gap.
+    Skipped(T)
+}
+
+// the day of a POSIX rule transition
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum RuleDay
+{
+    // `Jn`: from 1 to 365, not counting the 29th of February
+    Julian(i64),
+    // `n`: from 0 to 365, counting the 29th of February
+    Zero(i64),
+    // `Mm.w.d`: the day `d` (0 for Sunday) of the week `w` of the month `m`, 5 being the last
+    Month(u32, u32, u32)
+}
+
+impl RuleDay
+{
+    fn days_since_epoch(&self, year: i32) -> i64
+    {
+        let january_first = Date::new(year, 1, 1).unwrap().days_since_epoch();
+
+        match *self
+        {
+            RuleDay::Julian(day) => {
+                let leap_day = if day >= 60 && days_in_month(year, 2) == 29 {1} else {0};
+                january_first + day - 1 + leap_day
+            }
+            RuleDay::Zero(day) => january_first + day,
+            RuleDay::Month(month, week, weekday) => {
+                let first = Date::new(year, month, 1).unwrap();
+                let mut day = 1 + (weekday as i64 - first.weekday().number() as i64).rem_euclid(7) + 7 * (week as i64 - 1);
+                while day > days_in_month(year, month) as i64
+                {
+                    day -= 7;
+                }
+                first.days_since_epoch() + day - 1
+            }
+        }
+    }
+}
+
+// when daylight saving time starts or ends: a day, and a local time in seconds
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Transition
+{
+    day: RuleDay,
+    time: i64
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct DaylightSaving
+{
+    offset: i64,
+    start: Transition,
+    end: Transition
+}
+
+// a POSIX TZ rule like `CET-1CEST,M3.5.0,M10.5.0/3`, with its offsets in seconds east of UTC
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct PosixRule
+{
+    offset: i64,
+    daylight_saving: Option<DaylightSaving>
+}
+
+impl PosixRule
+{
+    fn offset_at(&self, timestamp: i64) -> i64
+    {
+        let dst = match self.daylight_saving
+        {
+            Some(dst) => dst,
+            None => return self.offset,
+        };
+
+        let year = Date::from_days_since_epoch((timestamp + self.offset).div_euclid(SECONDS_PER_DAY)).year();
+        // the start is given in standard time, and the end in daylight saving time
+        let start = dst.start.day.days_since_epoch(year) * SECONDS_PER_DAY + dst.start.time - self.offset;
+        let end = dst.end.day.days_since_epoch(year) * SECONDS_PER_DAY + dst.end.time - dst.offset;
+
+        // in the southern hemisphere, daylight saving time spans the new year
+        let in_daylight_saving = match start < end
+        {
+            true => start <= timestamp && timestamp < end,
+            false => timestamp < end || start <= timestamp,
+        };
+        if in_daylight_saving {dst.offset} else {self.offset}
+    }
+
+    fn parse(rule: &str) -> Option<PosixRule>
+    {
+        let mut parser = RuleParser { rest: rule };
+
+        parser.name()?;
+        let offset = -parser.time()?;
+        if parser.rest.is_empty()
+        {
+            return Some(PosixRule { offset, daylight_saving: None });
+        }
+
+        parser.name()?;
+        let dst_offset = match parser.rest.starts_with(',') || parser.rest.is_empty()
+        {
+            true => offset + 3600,
+            false => -parser.time()?,
+        };
+
+        // the United States rules are the default ones
+        let (start, end) = match parser.rest.is_empty()
+        {
+            true => (
+                Transition { day: RuleDay::Month(3, 2, 0), time: 7200 },
+                Transition { day: RuleDay::Month(11, 1, 0), time: 7200 }
+            ),
+            false => {
+                parser.rest = parser.rest.strip_prefix(',')?;
+                let start = parser.transition()?;
+                parser.rest = parser.rest.strip_prefix(',')?;
+                (start, parser.transition()?)
+            }
+        };
+
+        match parser.rest.is_empty()
+        {
+            true => Some(PosixRule {
+                offset,
+                daylight_saving: Some(DaylightSaving { offset: dst_offset, start, end })
+            }),
+            false => None,
+        }
+    }
+}
+
+struct RuleParser<'a>
+{
+    rest: &'a str
+}
+
+impl RuleParser<'_>
+{
+    // a zone abbreviation, like `CET` or `<-03>`
+    fn name(&mut self) -> Option<()>
+    {
+        let length = match self.rest.strip_prefix('<')
+        {
+            Some(quoted) => quoted.find('>')? + 2,
+            None => self.rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(self.rest.len()),
+        };
+        if length < 3
+        {
+            return None;
+        }
+
+        self.rest = &self.rest[length..];
+        Some(())
+    }
+
+    fn number(&mut self) -> Option<i64>
+    {
+        let length = self.rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(self.rest.len());
+        let number = self.rest[..length].parse().ok()?;
+        self.rest = &self.rest[length..];
+        Some(number)
+    }
+
+    // `[+-]hh[:mm[:ss]]`, in seconds
+    fn time(&mut self) -> Option<i64>
+    {
+        let sign = match self.rest.chars().next()?
+        {
+            '-' => -1,
+            _ => 1,
+        };
+        self.rest = self.rest.trim_start_matches(['+', '-']);
+
+        let mut seconds = self.number()? * 3600;
+        for unit in [60, 1]
+        {
+            match self.rest.strip_prefix(':')
+            {
+                Some(rest) => {
+                    self.rest = rest;
+                    seconds += self.number()? * unit;
+                }
+                None => break,
+            }
+        }
+
+        Some(sign * seconds)
+    }
+
+    // `Jn`, `n` or `Mm.w.d`, followed by an optional `/time`
+    fn transition(&mut self) -> Option<Transition>
+    {
+        let day = if let Some(rest) = self.rest.strip_prefix('J')
+        {
+            self.rest = rest;
+            RuleDay::Julian(self.number().filter(|day| (1..=365).contains(day))?)
+        }
+        else if let Some(rest) = self.rest.strip_prefix('M')
+        {
+            self.rest = rest;
+            let month = self.number().filter(|month| (1..=12).contains(month))?;
+            self.rest = self.rest.strip_prefix('.')?;
+            let week = self.number().filter(|week| (1..=5).contains(week))?;
+            self.rest = self.rest.strip_prefix('.')?;
+            let weekday = self.number().filter(|weekday| (0..=6).contains(weekday))?;
+            RuleDay::Month(month as u32, week as u32, weekday as u32)
+        }
+        else
+        {
+            RuleDay::Zero(self.number().filter(|day| (0..=365).contains(day))?)
+        };
+
+        let time = match self.rest.strip_prefix('/')
+        {
+            Some(rest) => {
+                self.rest = rest;
+                self.time()?
+            }
+            None => 7200,
+        };
+
+        Some(Transition { day, time })
+    }
+}
+
+// the contents of a TZif file: the instants when the offset changes, the offsets, and the rule
+// for the instants after the last change
+#[derive(Clone, PartialEq, Debug)]
+struct Tzif
+{
+    transitions: Vec<i64>,
+    transition_types: Vec<usize>,
+    offsets: Vec<i64>,
+    footer: Option<PosixRule>
+}
+
+struct Reader<'a>
+{
+    data: &'a [u8]
+}
+
+impl<'a> Reader<'a>
+{
+    fn take(&mut self, length: usize) -> Option<&'a [u8]>
+    {
+        if self.data.len() < length
+        {
+            return None;
+        }
+
+        let (taken, rest) = self.data.split_at(length);
+        self.data = rest;
+        Some(taken)
+    }
+
+    fn u32(&mut self) -> Option<usize>
+    {
+        Some(u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as usize)
+    }
+
+    // the counts of the header: UT indicators, standard indicators, leap seconds, transitions,
+    // types and abbreviation characters
+    fn header(&mut self) -> Option<(u8, [usize; 6])>
+    {
+        let magic = self.take(20)?;
+        if &magic[..4] != b"TZif"
+        {
+            return None;
+        }
+
+        let mut counts = [0; 6];
+        for count in counts.iter_mut()
+        {
+            *count = self.u32()?;
+        }
+        Some((magic[4], counts))
+    }
+}
+
+impl Tzif
+{
+    fn parse(data: &[u8]) -> Option<Tzif>
+    {
+        let mut reader = Reader { data };
+        let (version, mut counts) = reader.header()?;
+
+        // from version 2, the 32-bit data is followed by the same data in 64 bits and a footer
+        let time_size = match version
+        {
+            0 => 4,
+            _ => {
+                let [ut, standard, leap, transitions, types, characters] = counts;
+                reader.take(transitions * 5 + types * 6 + characters + leap * 8 + standard + ut)?;
+                counts = reader.header()?.1;
+                8
+            }
+        };
+        let [ut, standard, leap, transitions, types, characters] = counts;
+        if types == 0
+        {
+            return None;
+        }
+
+        let transitions: Vec<i64> = reader
+            .take(transitions * time_size)?
+            .chunks(time_size)
+            .map(|chunk| match time_size
+            {
+                4 => i32::from_be_bytes(chunk.try_into().unwrap()) as i64,
+                _ => i64::from_be_bytes(chunk.try_into().unwrap()),
+            })
+            .collect();
+        let transition_types: Vec<usize> = reader.take(transitions.len())?.iter().map(|&index| index as usize).collect();
+        let offsets: Vec<i64> = reader
+            .take(types * 6)?
+            .chunks(6)
+            .map(|chunk| i32::from_be_bytes(chunk[..4].try_into().unwrap()) as i64)
+            .collect();
+        if transition_types.iter().any(|&index| index >= types)
+        {
+            return None;
+        }
+        reader.take(characters + leap * (time_size + 4) + standard + ut)?;
+
+        let footer = match version
+        {
+            0 => None,
+            _ => {
+                let footer = std::str::from_utf8(reader.data).ok()?;
+                let rule = footer.strip_prefix('\n')?.strip_suffix('\n')?;
+                match rule.is_empty()
+                {
+                    true => None,
+                    false => Some(PosixRule::parse(rule)?),
+                }
+            }
+        };
+
+        Some(Tzif { transitions, transition_types, offsets, footer })
+    }
+
+    fn offset_at(&self, timestamp: i64) -> i64
+    {
+        match (self.transitions.partition_point(|&transition| transition <= timestamp), self.footer)
+        {
+            (after, Some(footer)) if after == self.transitions.len() => footer.offset_at(timestamp),
+            // the first type is the one from before the first transition
+            (0, _) => self.offsets[0],
+            (after, _) => self.offsets[self.transition_types[after - 1]],
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+enum Rules
+{
+    Fixed(i64),
+    Posix(PosixRule),
+    Tzif(Tzif)
+}
+
+/// A time zone: a fixed offset from UTC, or rules changing the offset over time.
+///
+/// The offsets are in whole seconds.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Zone
+{
+    name: String,
+    rules: Arc<Rules>
+}
+
+fn format_offset(offset: i64) -> String
+{
+    let sign = if offset < 0 {'-'} else {'+'};
+    let offset = offset.abs();
+    let mut text = format!("{}{:02}:{:02}", sign, offset / 3600, offset % 3600 / 60);
+    if offset % 60 != 0
+    {
+        text.push_str(&format!(":{:02}", offset % 60));
+    }
+    text
+}
+
+impl Zone
+{
+    pub fn utc() -> Zone
+    {
+        Zone { name: "UTC".to_string(), rules: Arc::new(Rules::Fixed(0)) }
+    }
+
+    /// A zone at a fixed offset east of UTC, named like `+05:30`, truncated to whole seconds.
+    pub fn fixed(offset: Duration) -> Zone
+    {
+        let offset = offset.as_seconds();
+        Zone { name: format_offset(offset), rules: Arc::new(Rules::Fixed(offset)) }
+    }
+
+    /// A zone following a POSIX TZ rule, like `CET-1CEST,M3.5.0,M10.5.0/3`, which is its name.
+    pub fn posix(rule: &str) -> Result<Zone, ZoneError>
+    {
+        let rule = PosixRule::parse(rule).map(|parsed| (rule, parsed)).ok_or(ZoneError::InvalidRule)?;
+        Ok(Zone { name: rule.0.to_string(), rules: Arc::new(Rules::Posix(rule.1)) })
+    }
+
+    /// A zone with the current rules of the embedded zones, which are some of the most used
+    /// tzdata zones, like `Europe/Paris`: the past offsets may be wrong.
+    pub fn embedded(name: &str) -> Result<Zone, ZoneError>
+    {
+        let (_, rule) = EMBEDDED.iter().find(|(embedded, _)| *embedded == name).ok_or(ZoneError::UnknownZone)?;
+        let rule = PosixRule::parse(rule).unwrap();
+        Ok(Zone { name: name.to_string(), rules: Arc::new(Rules::Posix(rule)) })
+    }
+
+    /// A zone read from the contents of a TZif file, the format of the tzdata files.
+    pub fn from_tzif(name: &str, data: &[u8]) -> Result<Zone, ZoneError>
+    {
+        let tzif = Tzif::parse(data).ok_or(ZoneError::InvalidData)?;
+        Ok(Zone { name: name.to_string(), rules: Arc::new(Rules::Tzif(tzif)) })
+    }
+
+    /// A zone read from the local tzdata, in the `TZDIR` directory or in `/usr/share/zoneinfo`.
+    pub fn from_tzdata(name: &str) -> Result<Zone, ZoneError>
+    {
+        // the name must stay in the tzdata directory
+        if name.is_empty() || name.starts_with('/') || name.split('/').any(|part| part == "..")
+        {
+            return Err(ZoneError::UnknownZone);
+        }
+
+        let directory = env::var_os("TZDIR").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("/usr/share/zoneinfo"));
+        let data = fs::read(directory.join(name)).map_err(|_| ZoneError::UnknownZone)?;
+        Zone::from_tzif(name, &data)
+    }
+
+    /// The zone from the local tzdata, or from the embedded zones when there is no such file.
+    pub fn named(name: &str) -> Result<Zone, ZoneError>
+    {
+        match Zone::from_tzdata(name)
+        {
+            Err(ZoneError::UnknownZone) => Zone::embedded(name),
+            zone => zone,
+        }
+    }
+
+    pub fn name(&self) -> &str
+    {
+        &self.name
+    }
+
+    // in seconds
+    fn offset_seconds(&self, timestamp: i64) -> i64
+    {
+        match self.rules.as_ref()
+        {
+            Rules::Fixed(offset) => *offset,
+            Rules::Posix(rule) => rule.offset_at(timestamp),
+            Rules::Tzif(tzif) => tzif.offset_at(timestamp),
+        }
+    }
+
+    /// The offset from UTC at an instant, given in milliseconds since 1970-01-01 00:00 UTC.
+    pub fn offset_at(&self, timestamp: i64) -> Duration
+    {
+        Duration::from_seconds(self.offset_seconds(timestamp.div_euclid(MILLISECONDS_PER_SECOND)))
+    }
+
+    /// The offsets from UTC the zone can have at a wall time.
+    pub fn resolve(&self, date: Date, clock: &Clock) -> LocalResult<Duration>
+    {
+        let local = wall_milliseconds(date, clock);
+        // no zone changes its offset twice in two days
+        let before = self.offset_at(local - MILLISECONDS_PER_DAY);
+        let after = self.offset_at(local + MILLISECONDS_PER_DAY);
+        let valid = |offset: Duration| self.offset_at(local - offset.as_milliseconds()) == offset;
+
+        match (valid(before), valid(after))
+        {
+            (true, true) if before != after => LocalResult::Ambiguous(before.max(after), before.min(after)),
+            (true, _) => LocalResult::Single(before),
+            (false, true) => LocalResult::Single(after),
+            (false, false) => LocalResult::Skipped(before),
+        }
+    }
+}
+
+// the wall time as milliseconds since 1970-01-01 00:00, as if it were in UTC
+fn wall_milliseconds(date: Date, clock: &Clock) -> i64
+{
+    date.days_since_epoch() * MILLISECONDS_PER_DAY + clock.milliseconds_since_midnight()
+}
+
+/// A date and a wall time in a zone.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ZonedClock
+{
+    date: Date,
+    clock: Clock,
+    offset: Duration,
+    zone: Zone
+}
+
+impl ZonedClock
+{
+    /// The wall time on a date in a zone. The wall times skipped when the clocks go forward
+    /// are moved forward by the length of the gap, and the wall times repeated when the clocks
+    /// go back are taken at their first occurrence.
+    ///
+    /// The clock is read as the real time since the top of its dial, so that a clock on the
+    /// 12-hour dial is taken in the morning.
+    pub fn new(date: Date, clock: Clock, zone: &Zone) -> ZonedClock
+    {
+        match ZonedClock::resolve(date, clock, zone)
+        {
+            LocalResult::Single(zoned) | LocalResult::Ambiguous(zoned, _) | LocalResult::Skipped(zoned) => zoned,
+        }
+    }
+
+    pub fn resolve(date: Date, clock: Clock, zone: &Zone) -> LocalResult<ZonedClock>
+    {
+        let local = wall_milliseconds(date, &clock);
+        let zoned = |offset: Duration| ZonedClock::from_timestamp(local - offset.as_milliseconds(), zone);
+
+        match zone.resolve(date, &clock)
+        {
+            LocalResult::Single(offset) => LocalResult::Single(zoned(offset)),
+            LocalResult::Ambiguous(earlier, later) => LocalResult::Ambiguous(zoned(earlier), zoned(later)),
+            LocalResult::Skipped(offset) => LocalResult::Skipped(zoned(offset)),
+        }
+    }
+
+    /// The wall time in a zone at an instant, given in milliseconds since 1970-01-01 00:00 UTC.
+    pub fn from_timestamp(timestamp: i64, zone: &Zone) -> ZonedClock
+    {
+        let offset = zone.offset_at(timestamp);
+        let local = timestamp + offset.as_milliseconds();
+
+        ZonedClock
+        {
+            date: Date::from_days_since_epoch(local.div_euclid(MILLISECONDS_PER_DAY)),
+            clock: Clock::from_milliseconds(local),
+            offset,
+            zone: zone.clone()
+        }
+    }
+
+    /// The instant, in milliseconds since 1970-01-01 00:00 UTC.
+    pub fn timestamp(&self) -> i64
+    {
+        wall_milliseconds(self.date, &self.clock) - self.offset.as_milliseconds()
+    }
+
+    /// The wall time in another zone at the same instant.
+    pub fn to_zone(&self, zone: &Zone) -> ZonedClock
+    {
+        ZonedClock::from_timestamp(self.timestamp(), zone)
+    }
+
+    pub fn date(&self) -> Date
+    {
+        self.date
+    }
+
+    pub fn clock(&self) -> &Clock
+    {
+        &self.clock
+    }
+
+    /// The offset from UTC, east of it being positive.
+    pub fn offset(&self) -> Duration
+    {
+        self.offset
+    }
+
+    pub fn zone(&self) -> &Zone
+    {
+        &self.zone
+    }
+}
+
+/// Prints the date, the wall time and the offset, like `2024-03-31 03:30+02:00`.
+impl fmt::Display for ZonedClock
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}{}", self.date, self.clock, format_offset(self.offset.as_seconds()))
+    }
+}
@@ -0,0 +1,106 @@
+use clock::{Clock, Dial, Duration};
+
+//
+// Dials
+//
+
+#[test]
+fn test_twenty_four_hour_dial_is_the_default() {
+    assert_eq!(Dial::default(), Dial::TWENTY_FOUR_HOUR);
+    assert_eq!(Clock::new(25, 61).dial(), Dial::TWENTY_FOUR_HOUR);
+    assert_eq!(Dial::default().clock(25, 61), Clock::new(25, 61));
+}
+
+#[test]
+fn test_invalid_dials() {
+    assert_eq!(Dial::new(0, 60, 60, Duration::from_hours(1)), None);
+    assert_eq!(Dial::new(10, -100, 100, Duration::from_hours(24)), None);
+    assert_eq!(Dial::new(10, 100, 100, Duration::from_hours(0)), None);
+    assert_eq!(Dial::shift(0), None);
+}
+
+#[test]
+fn test_twelve_hour_dial() {
+    let dial = Dial::TWELVE_HOUR;
+    assert_eq!(dial.clock(13, 30).to_string(), "01:30");
+    assert_eq!(dial.clock(12, 0).to_string(), "00:00");
+    assert_eq!(dial.clock(-1, 0).to_string(), "11:00");
+    assert_eq!(dial.clock(11, 0).add_minutes(90).to_string(), "00:30");
+}
+
+#[test]
+fn test_decimal_dial() {
+    let dial = Dial::DECIMAL;
+    assert_eq!(dial.clock(3, 150).to_string(), "04:50");
+    assert_eq!(dial.clock_with_seconds(9, 99, 100).to_string(), "00:00");
+    assert_eq!(dial.clock(0, -1).to_string(), "09:99");
+}
+
+#[test]
+fn test_shift_dial() {
+    let dial = Dial::shift(8).unwrap();
+    assert_eq!(dial.hours(), 8);
+    assert_eq!(dial.period(), Duration::from_hours(8));
+    assert_eq!(dial.clock(9, 15).to_string(), "01:15");
+    assert_eq!(dial.clock(7, 0) + Duration::from_hours(2), dial.clock(1, 0));
+}
+
+#[test]
+fn test_custom_dial() {
+    // a 20-hour day of 50-minute hours and 60-second minutes
+    let dial = Dial::new(20, 50, 60, Duration::from_hours(24)).unwrap();
+    assert_eq!(dial.clock(0, 75).to_string(), "01:25");
+    assert_eq!(dial.clock(10, 0).to_dial(Dial::TWENTY_FOUR_HOUR), Clock::new(12, 0));
+}
+
+//
+// Conversions and arithmetic
+//
+
+#[test]
+fn test_to_dial() {
+    assert_eq!(Clock::new(18, 0).to_dial(Dial::DECIMAL), Dial::DECIMAL.clock(7, 50));
+    assert_eq!(Clock::new(18, 0).to_dial(Dial::TWELVE_HOUR), Dial::TWELVE_HOUR.clock(6, 0));
+    assert_eq!(Dial::DECIMAL.clock(5, 0).to_dial(Dial::default()), Clock::new(12, 0));
+    assert_eq!(Clock::new(18, 0).to_dial(Dial::DECIMAL).to_dial(Dial::default()), Clock::new(18, 0));
+}
+
+#[test]
+fn test_to_dial_rounds_down() {
+    // a decimal second is 864 real milliseconds
+    assert_eq!(Clock::with_seconds(0, 0, 1).to_dial(Dial::DECIMAL), Dial::DECIMAL.clock_with_milliseconds(0, 0, 1, 157));
+}
+
+#[test]
+fn test_durations_are_real_time_on_every_dial() {
+    assert_eq!(Dial::DECIMAL.clock(0, 0) + Duration::from_hours(12), Dial::DECIMAL.clock(5, 0));
+    assert_eq!(Dial::DECIMAL.clock(0, 0) - Duration::from_hours(6), Dial::DECIMAL.clock(7, 50));
+
+    let mut clock = Dial::TWELVE_HOUR.clock(10, 0);
+    clock += Duration::from_hours(3);
+    assert_eq!(clock, Dial::TWELVE_HOUR.clock(1, 0));
+}
+
+#[test]
+fn test_difference_on_a_dial() {
+    let dial = Dial::TWELVE_HOUR;
+    assert_eq!(dial.clock(1, 0) - dial.clock(11, 0), Duration::from_hours(2));
+    assert_eq!(dial.clock(11, 0) - dial.clock(1, 0), Duration::from_hours(-2));
+    assert_eq!(dial.clock(6, 0) - dial.clock(0, 0), Duration::from_hours(6));
+    assert_eq!(Dial::DECIMAL.clock(5, 0) - Dial::DECIMAL.clock(0, 0), Duration::from_hours(12));
+}
+
+#[test]
+fn test_clocks_on_different_dials_differ() {
+    assert_ne!(Dial::TWELVE_HOUR.clock(1, 0), Clock::new(1, 0));
+}
+
+#[test]
+fn test_clocks_on_different_dials_are_ordered_by_time() {
+    assert!(Dial::DECIMAL.clock(9, 0) > Clock::new(10, 0));
+    assert!(Dial::DECIMAL.clock(4, 0) < Clock::new(10, 0));
+
+    let mut clocks = vec![Dial::DECIMAL.clock(9, 0), Clock::new(10, 0), Dial::TWELVE_HOUR.clock(1, 0)];
+    clocks.sort();
+    assert_eq!(clocks, [Dial::TWELVE_HOUR.clock(1, 0), Clock::new(10, 0), Dial::DECIMAL.clock(9, 0)]);
+}
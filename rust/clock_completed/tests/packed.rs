@@ -0,0 +1,66 @@
+use std::collections::{BTreeSet, HashMap};
+
+use clock::{Clock, Dial};
+
+//
+// Ordering and hashing
+//
+
+#[test]
+fn test_clocks_are_ordered_from_midnight() {
+    let mut clocks = vec![Clock::new(13, 0), Clock::new(0, 5), Clock::with_seconds(12, 59, 59), Clock::new(-1, 0)];
+    clocks.sort();
+    assert_eq!(clocks, vec![Clock::new(0, 5), Clock::with_seconds(12, 59, 59), Clock::new(13, 0), Clock::new(23, 0)]);
+}
+
+#[test]
+fn test_clocks_as_keys() {
+    let mut alarms = HashMap::new();
+    alarms.insert(Clock::new(7, 0), "wake up");
+    alarms.insert(Clock::new(31, 0), "wake up again");
+    assert_eq!(alarms.len(), 1);
+    assert_eq!(alarms[&Clock::new(7, 0)], "wake up again");
+
+    let set: BTreeSet<Clock> = [Clock::new(9, 0), Clock::new(8, 0), Clock::new(9, 0)].into_iter().collect();
+    assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![Clock::new(8, 0), Clock::new(9, 0)]);
+}
+
+#[test]
+fn test_clocks_are_copied() {
+    let clock = Clock::new(10, 0);
+    let copy = clock;
+    assert_eq!(clock, copy);
+}
+
+//
+// Packed representation
+//
+
+#[test]
+fn test_to_packed() {
+    assert_eq!(Clock::new(0, 0).to_packed(), 0);
+    assert_eq!(Clock::new(10, 37).to_packed(), 637);
+    assert_eq!(Clock::new(23, 59).to_packed(), 1439);
+    assert_eq!(Clock::with_milliseconds(10, 37, 59, 999).to_packed(), 637);
+}
+
+#[test]
+fn test_from_packed() {
+    assert_eq!(Clock::from_packed(637), Some(Clock::new(10, 37)));
+    assert_eq!(Clock::from_packed(1439), Some(Clock::new(23, 59)));
+    assert_eq!(Clock::from_packed(1440), None);
+    assert_eq!(Clock::from_packed(u16::MAX), None);
+}
+
+#[test]
+fn test_packed_round_trip_keeps_the_order() {
+    let packed: Vec<u16> = (0..1440).map(|minutes| Clock::new(0, minutes).to_packed()).collect();
+    assert!(packed.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(packed.iter().all(|&packed| Clock::from_packed(packed).unwrap().to_packed() == packed));
+}
+
+#[test]
+fn test_packed_is_on_the_twenty_four_hour_dial() {
+    assert_eq!(Dial::DECIMAL.clock(5, 0).to_packed(), 720);
+    assert_eq!(Dial::TWELVE_HOUR.clock(1, 30).to_packed(), 90);
+}
@@ -0,0 +1,133 @@
+use clock::{Clock, Duration, ParseClockError};
+
+//
+// Seconds and Milliseconds
+//
+
+#[test]
+fn test_seconds_are_only_shown_when_set() {
+    assert_eq!(Clock::with_seconds(8, 5, 0).to_string(), "08:05");
+    assert_eq!(Clock::with_seconds(8, 5, 9).to_string(), "08:05:09");
+    assert_eq!(Clock::with_milliseconds(8, 5, 0, 7).to_string(), "08:05:00.007");
+}
+
+#[test]
+fn test_seconds_roll_over() {
+    assert_eq!(Clock::with_seconds(23, 59, 60), Clock::new(0, 0));
+    assert_eq!(Clock::with_seconds(0, 0, -1).to_string(), "23:59:59");
+    assert_eq!(Clock::with_milliseconds(0, 0, 0, -1).to_string(), "23:59:59.999");
+    assert_eq!(Clock::with_milliseconds(1, 0, 0, 3_600_000), Clock::new(2, 0));
+}
+
+#[test]
+fn test_large_units_do_not_overflow() {
+    assert_eq!(Clock::with_milliseconds(i32::MAX, i32::MAX, i32::MAX, i32::MAX).to_string(), "08:52:30.647");
+}
+
+#[test]
+fn test_getters() {
+    let clock = Clock::with_milliseconds(13, 4, 5, 6);
+
+    assert_eq!((clock.hours(), clock.minutes(), clock.seconds(), clock.milliseconds()), (13, 4, 5, 6));
+}
+
+#[test]
+fn test_add_seconds_and_milliseconds() {
+    assert_eq!(Clock::new(10, 0).add_seconds(-30).to_string(), "09:59:30");
+    assert_eq!(Clock::new(23, 59).add_seconds(90).to_string(), "00:00:30");
+    assert_eq!(Clock::with_seconds(10, 0, 30).add_minutes(1).to_string(), "10:01:30");
+    assert_eq!(Clock::new(0, 0).add_milliseconds(1500).to_string(), "00:00:01.500");
+}
+
+//
+// Parsing
+//
+
+#[test]
+fn test_parse_24_hours() {
+    assert_eq!("08:05".parse(), Ok(Clock::new(8, 5)));
+    assert_eq!("8:05".parse(), Ok(Clock::new(8, 5)));
+    assert_eq!("23:59:58".parse(), Ok(Clock::with_seconds(23, 59, 58)));
+    assert_eq!("00:00:01.250".parse(), Ok(Clock::with_milliseconds(0, 0, 1, 250)));
+    assert_eq!(" 12:00 ".parse(), Ok(Clock::new(12, 0)));
+}
+
+#[test]
+fn test_parse_12_hours() {
+    assert_eq!("7:05 pm".parse(), Ok(Clock::new(19, 5)));
+    assert_eq!("7:05am".parse(), Ok(Clock::new(7, 5)));
+    assert_eq!("12:30 AM".parse(), Ok(Clock::new(0, 30)));
+    assert_eq!("12:30 PM".parse(), Ok(Clock::new(12, 30)));
+    assert_eq!("11:59:59 pm".parse(), Ok(Clock::with_seconds(23, 59, 59)));
+}
+
+#[test]
+fn test_parse_what_is_displayed() {
+    for clock in [Clock::new(0, 0), Clock::with_seconds(9, 8, 7), Clock::with_milliseconds(23, 0, 0, 10)]
+    {
+        assert_eq!(clock.to_string().parse::<Clock>(), Ok(clock));
+    }
+}
+
+#[test]
+fn test_parse_invalid_format() {
+    for s in ["", "8", "8:5", "08:05:", "08:05:1", "08:05:01.5", "08:05:01:02", "108:00", "ab:cd", "+8:05", "8:05 xm", "am"]
+    {
+        assert_eq!(s.parse::<Clock>(), Err(ParseClockError::InvalidFormat), "{}", s);
+    }
+}
+
+#[test]
+fn test_parse_out_of_range() {
+    for s in ["24:00", "12:60", "12:00:60", "0:30 am", "13:00 pm"]
+    {
+        assert_eq!(s.parse::<Clock>(), Err(ParseClockError::OutOfRange), "{}", s);
+    }
+}
+
+//
+// Durations
+//
+
+#[test]
+fn test_add_and_subtract_durations() {
+    assert_eq!(Clock::new(10, 0) + Duration::from_minutes(90), Clock::new(11, 30));
+    assert_eq!(Clock::new(1, 0) - Duration::from_hours(2), Clock::new(23, 0));
+    assert_eq!(Clock::new(0, 0) + Duration::from_hours(24 * 365), Clock::new(0, 0));
+    assert_eq!(Clock::new(0, 0) - Duration::from_milliseconds(1), Clock::with_milliseconds(23, 59, 59, 999));
+}
+
+#[test]
+fn test_assign_durations() {
+    let mut clock = Clock::new(12, 0);
+    clock += Duration::from_seconds(45);
+    assert_eq!(clock.to_string(), "12:00:45");
+
+    clock -= Duration::from_minutes(1);
+    assert_eq!(clock.to_string(), "11:59:45");
+}
+
+#[test]
+fn test_duration_arithmetic() {
+    let duration = Duration::from_hours(1) - Duration::from_seconds(90);
+
+    assert_eq!(duration.as_minutes(), 58);
+    assert_eq!(duration.as_seconds(), 3510);
+    assert_eq!((-duration).as_milliseconds(), -3_510_000);
+    assert_eq!(duration + Duration::from_seconds(90), Duration::from_minutes(60));
+}
+
+#[test]
+fn test_difference_between_clocks() {
+    assert_eq!(Clock::new(10, 30) - Clock::new(9, 0), Duration::from_minutes(90));
+    assert_eq!(Clock::new(9, 0) - Clock::new(10, 30), Duration::from_minutes(-90));
+}
+
+#[test]
+fn test_difference_takes_the_short_way_around_the_dial() {
+    assert_eq!(Clock::new(0, 30) - Clock::new(23, 30), Duration::from_hours(1));
+    assert_eq!(Clock::new(23, 30) - Clock::new(0, 30), Duration::from_hours(-1));
+    assert_eq!(Clock::new(18, 0) - Clock::new(6, 0), Duration::from_hours(12));
+    assert_eq!(Clock::new(6, 0) - Clock::new(18, 0), Duration::from_hours(12));
+    assert_eq!(Clock::with_seconds(18, 0, 1) - Clock::new(6, 0), Duration::from_seconds(-12 * 3600 + 1));
+}
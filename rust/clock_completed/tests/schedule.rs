@@ -0,0 +1,223 @@
+use clock::*;
+
+fn date(year: i32, month: u32, day: u32) -> Date {
+    Date::new(year, month, day).unwrap()
+}
+
+fn cron(expression: &str) -> Cron {
+    expression.parse().unwrap()
+}
+
+fn range(text: &str) -> TimeRange {
+    text.parse().unwrap()
+}
+
+// the next firings as `YYYY-MM-DD HH:MM`
+fn firings(schedule: &Schedule, from: Date, clock: Clock, count: usize) -> Vec<String> {
+    schedule.upcoming(from, &clock).take(count).map(|firing| format!("{} {}", firing.date(), firing.clock())).collect()
+}
+
+//
+// Weekdays and alarms
+//
+
+#[test]
+fn test_weekdays() {
+    assert!(Weekdays::WORKDAYS.contains(Weekday::Monday));
+    assert!(!Weekdays::WORKDAYS.contains(Weekday::Sunday));
+    assert_eq!(Weekdays::from_days(&[Weekday::Sunday, Weekday::Saturday]), Weekdays::WEEKEND);
+    assert_eq!(Weekdays::EVERY_DAY.without(Weekday::Sunday).without(Weekday::Saturday), Weekdays::WORKDAYS);
+    assert!(Weekdays::NONE.is_empty());
+}
+
+#[test]
+fn test_alarm_later_today() {
+    let alarm = Alarm::daily(Clock::new(7, 30));
+    assert_eq!(alarm.next_from(date(2024, 3, 4), &Clock::new(6, 0)), Some((date(2024, 3, 4), Clock::new(7, 30))));
+    assert_eq!(alarm.next_from(date(2024, 3, 4), &Clock::new(7, 30)), Some((date(2024, 3, 4), Clock::new(7, 30))));
+}
+
+#[test]
+fn test_alarm_tomorrow() {
+    let alarm = Alarm::daily(Clock::new(7, 30));
+    assert_eq!(alarm.next_from(date(2024, 12, 31), &Clock::with_seconds(7, 30, 1)), Some((date(2025, 1, 1), Clock::new(7, 30))));
+}
+
+#[test]
+fn test_alarm_skips_to_its_weekdays() {
+    let alarm = Alarm::new(Clock::new(7, 0), Weekdays::WORKDAYS);
+    // 2024-03-08 is a Friday
+    assert_eq!(alarm.next_from(date(2024, 3, 8), &Clock::new(8, 0)), Some((date(2024, 3, 11), Clock::new(7, 0))));
+
+    let alarm = Alarm::new(Clock::new(7, 0), Weekdays::from_days(&[Weekday::Friday]));
+    assert_eq!(alarm.next_from(date(2024, 3, 8), &Clock::new(8, 0)), Some((date(2024, 3, 15), Clock::new(7, 0))));
+}
+
+#[test]
+fn test_alarm_without_weekdays_never_rings() {
+    assert_eq!(Alarm::new(Clock::new(7, 0), Weekdays::NONE).next_from(date(2024, 3, 4), &Clock::new(0, 0)), None);
+}
+
+//
+// Cron expressions
+//
+
+#[test]
+fn test_cron_matches() {
+    let cron = cron("*/15 9-17 * * mon-fri");
+    assert!(cron.matches(date(2024, 3, 4), &Clock::new(9, 45)));
+    assert!(!cron.matches(date(2024, 3, 4), &Clock::new(9, 50)));
+    assert!(!cron.matches(date(2024, 3, 4), &Clock::new(18, 0)));
+    assert!(!cron.matches(date(2024, 3, 3), &Clock::new(9, 45)));
+    assert!(!cron.matches(date(2024, 3, 4), &Clock::with_seconds(9, 45, 30)));
+}
+
+#[test]
+fn test_cron_next() {
+    let cron = cron("30 2 * * *");
+    assert_eq!(cron.next_from(date(2024, 3, 4), &Clock::new(1, 0)), Some((date(2024, 3, 4), Clock::new(2, 30))));
+    assert_eq!(cron.next_from(date(2024, 3, 4), &Clock::new(2, 30)), Some((date(2024, 3, 4), Clock::new(2, 30))));
+    assert_eq!(cron.next_from(date(2024, 3, 4), &Clock::with_milliseconds(2, 30, 0, 1)), Some((date(2024, 3, 5), Clock::new(2, 30))));
+}
+
+#[test]
+fn test_cron_months_and_days() {
+    assert_eq!(cron("0 0 29 feb *").next_from(date(2025, 1, 1), &Clock::new(0, 0)), Some((date(2028, 2, 29), Clock::new(0, 0))));
+    assert_eq!(cron("0 12 1 */3 *").next_from(date(2024, 5, 2), &Clock::new(0, 0)), Some((date(2024, 7, 1), Clock::new(12, 0))));
+    assert_eq!(cron("0 0 30 2 *").next_from(date(2024, 1, 1), &Clock::new(0, 0)), None);
+}
+
+#[test]
+fn test_cron_day_fields_match_either() {
+    // the 13th, and every Friday
+    let cron = cron("0 0 13 * 5");
+    assert!(cron.matches(date(2024, 3, 13), &Clock::new(0, 0)));
+    assert!(cron.matches(date(2024, 3, 8), &Clock::new(0, 0)));
+    assert!(!cron.matches(date(2024, 3, 9), &Clock::new(0, 0)));
+}
+
+#[test]
+fn test_cron_sunday_is_zero_and_seven() {
+    assert_eq!(cron("0 0 * * 7"), cron("0 0 * * 0"));
+    assert_eq!(cron("0 0 * * SUN"), cron("0 0 * * 0"));
+    assert_eq!(cron("0 0 * * 5-7"), cron("0 0 * * 0,5,6"));
+}
+
+#[test]
+fn test_cron_macros() {
+    assert_eq!(cron("@daily"), cron("0 0 * * *"));
+    assert_eq!(cron("@weekly"), cron("0 0 * * sun"));
+    assert_eq!(cron("@yearly"), cron("0 0 1 jan *"));
+}
+
+#[test]
+fn test_invalid_cron() {
+    assert_eq!("* * * *".parse::<Cron>(), Err(ParseCronError::InvalidFormat));
+    assert_eq!("60 * * * *".parse::<Cron>(), Err(ParseCronError::OutOfRange));
+    assert_eq!("* * 0 * *".parse::<Cron>(), Err(ParseCronError::OutOfRange));
+    assert_eq!("*/0 * * * *".parse::<Cron>(), Err(ParseCronError::InvalidField));
+    assert_eq!("5-1 * * * *".parse::<Cron>(), Err(ParseCronError::InvalidField));
+    assert_eq!("* * * smarch *".parse::<Cron>(), Err(ParseCronError::InvalidField));
+    assert_eq!("1,,2 * * * *".parse::<Cron>(), Err(ParseCronError::InvalidField));
+}
+
+//
+// Time ranges
+//
+
+#[test]
+fn test_range_contains() {
+    let range = range("09:00-17:00");
+    assert!(range.contains(&Clock::new(9, 0)));
+    assert!(range.contains(&Clock::new(16, 59)));
+    assert!(!range.contains(&Clock::new(17, 0)));
+    assert!(!range.wraps_past_midnight());
+    assert_eq!(range.duration(), Duration::from_hours(8));
+}
+
+#[test]
+fn test_range_wrapping_past_midnight() {
+    let range = range("22:00-06:30");
+    assert!(range.wraps_past_midnight());
+    assert!(range.contains(&Clock::new(23, 0)));
+    assert!(range.contains(&Clock::new(3, 0)));
+    assert!(!range.contains(&Clock::new(12, 0)));
+    assert_eq!(range.duration(), Duration::from_minutes(510));
+}
+
+#[test]
+fn test_range_of_the_whole_day() {
+    let range = range("08:00-08:00");
+    assert!(range.contains(&Clock::new(7, 59)));
+    assert_eq!(range.duration(), Duration::from_hours(24));
+}
+
+#[test]
+fn test_range_overlaps() {
+    assert!(range("09:00-12:00").overlaps(&range("11:00-13:00")));
+    assert!(!range("09:00-12:00").overlaps(&range("12:00-13:00")));
+    assert!(range("22:00-02:00").overlaps(&range("01:00-03:00")));
+    assert!(range("22:00-02:00").overlaps(&range("23:00-23:30")));
+    assert!(!range("22:00-02:00").overlaps(&range("02:00-22:00")));
+    assert!(range("00:00-00:00").overlaps(&range("05:00-05:01")));
+}
+
+#[test]
+fn test_overlapping_ranges() {
+    let ranges = vec![range("08:00-12:00"), range("13:00-17:00"), range("11:30-13:30"), range("23:00-07:00")];
+    assert_eq!(overlapping(&ranges), vec![(0, 2), (1, 2)]);
+}
+
+#[test]
+fn test_invalid_range() {
+    assert_eq!("09:00".parse::<TimeRange>(), Err(ParseClockError::InvalidFormat));
+    assert_eq!("09:00-25:00".parse::<TimeRange>(), Err(ParseClockError::OutOfRange));
+}
+
+//
+// Schedules
+//
+
+#[test]
+fn test_upcoming_firings() {
+    let mut schedule = Schedule::new();
+    assert_eq!(schedule.add(Alarm::new(Clock::new(7, 0), Weekdays::WORKDAYS)), 0);
+    assert_eq!(schedule.add(cron("0 12 * * *")), 1);
+
+    // from Friday morning
+    assert_eq!(
+        firings(&schedule, date(2024, 3, 8), Clock::new(8, 0), 5),
+        vec!["2024-03-08 12:00", "2024-03-09 12:00", "2024-03-10 12:00", "2024-03-11 07:00", "2024-03-11 12:00"]
+    );
+}
+
+#[test]
+fn test_simultaneous_firings_follow_the_triggers() {
+    let mut schedule = Schedule::new();
+    schedule.add(cron("0 * * * *"));
+    schedule.add(Alarm::daily(Clock::new(1, 0)));
+
+    let upcoming: Vec<usize> = schedule.upcoming(date(2024, 3, 4), &Clock::new(0, 30)).take(3).map(|firing| firing.trigger()).collect();
+    assert_eq!(upcoming, vec![0, 1, 0]);
+}
+
+#[test]
+fn test_upcoming_firings_cross_the_year() {
+    let mut schedule = Schedule::new();
+    schedule.add(cron("59 23 31 12 *"));
+    schedule.add(cron("@yearly"));
+
+    assert_eq!(
+        firings(&schedule, date(2024, 12, 31), Clock::new(23, 59), 3),
+        vec!["2024-12-31 23:59", "2025-01-01 00:00", "2025-12-31 23:59"]
+    );
+}
+
+#[test]
+fn test_empty_schedule() {
+    assert_eq!(Schedule::new().upcoming(date(2024, 3, 4), &Clock::new(0, 0)).next(), None);
+
+    let mut schedule = Schedule::new();
+    schedule.add(cron("0 0 31 4 *"));
+    assert_eq!(schedule.upcoming(date(2024, 3, 4), &Clock::new(0, 0)).next(), None);
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "serde")]
+
+use clock::{Clock, Dial};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Shift {
+    start: Clock,
+    #[serde(with = "clock::minutes_since_midnight")]
+    end: Clock,
+}
+
+//
+// Text
+//
+
+#[test]
+fn test_serialize_as_text() {
+    assert_eq!(serde_json::to_string(&Clock::new(7, 5)).unwrap(), "\"07:05\"");
+    assert_eq!(serde_json::to_string(&Clock::with_milliseconds(7, 5, 30, 250)).unwrap(), "\"07:05:30.250\"");
+}
+
+#[test]
+fn test_serialize_on_the_twenty_four_hour_dial() {
+    assert_eq!(serde_json::to_string(&Dial::DECIMAL.clock(5, 0)).unwrap(), "\"12:00\"");
+}
+
+#[test]
+fn test_deserialize_from_text() {
+    assert_eq!(serde_json::from_str::<Clock>("\"07:05\"").unwrap(), Clock::new(7, 5));
+    assert_eq!(serde_json::from_str::<Clock>("\"7:05 pm\"").unwrap(), Clock::new(19, 5));
+    assert!(serde_json::from_str::<Clock>("\"25:00\"").is_err());
+}
+
+#[test]
+fn test_deserialize_text_only_from_text() {
+    assert!(serde_json::from_str::<Clock>("425").is_err());
+}
+
+//
+// Binary
+//
+
+#[test]
+fn test_binary_round_trip() {
+    let clock = Clock::new(7, 5);
+    let bytes = bincode::serialize(&clock).unwrap();
+    assert_eq!(bytes, 425u16.to_le_bytes());
+    assert_eq!(bincode::deserialize::<Clock>(&bytes).unwrap(), clock);
+
+    let shift = Shift { start: Clock::new(22, 0), end: Clock::new(6, 30) };
+    let bytes = bincode::serialize(&shift).unwrap();
+    assert_eq!(bincode::deserialize::<Shift>(&bytes).unwrap(), shift);
+}
+
+#[test]
+fn test_binary_drops_the_seconds() {
+    let bytes = bincode::serialize(&Clock::with_seconds(7, 5, 30)).unwrap();
+    assert_eq!(bincode::deserialize::<Clock>(&bytes).unwrap(), Clock::new(7, 5));
+}
+
+#[test]
+fn test_binary_rejects_minutes_past_the_day() {
+    assert!(bincode::deserialize::<Clock>(&1440u16.to_le_bytes()).is_err());
+}
+
+//
+// Minutes since midnight
+//
+
+#[test]
+fn test_minutes_since_midnight() {
+    let shift = Shift { start: Clock::new(22, 0), end: Clock::new(6, 30) };
+    let json = serde_json::to_string(&shift).unwrap();
+    assert_eq!(json, r#"{"start":"22:00","end":390}"#);
+    assert_eq!(serde_json::from_str::<Shift>(&json).unwrap(), shift);
+}
+
+#[test]
+fn test_minutes_since_midnight_rejects_text() {
+    assert!(serde_json::from_str::<Shift>(r#"{"start":"22:00","end":"06:30"}"#).is_err());
+    assert!(serde_json::from_str::<Shift>(r#"{"start":"22:00","end":1440}"#).is_err());
+}
@@ -0,0 +1,248 @@
+use clock::*;
+
+fn date(year: i32, month: u32, day: u32) -> Date {
+    Date::new(year, month, day).unwrap()
+}
+
+fn zoned(zone: &str, year: i32, month: u32, day: u32, hours: i32, minutes: i32) -> String {
+    ZonedClock::new(date(year, month, day), Clock::new(hours, minutes), &Zone::embedded(zone).unwrap()).to_string()
+}
+
+// a version 1 TZif file at +01:00 from 0 to 3600 seconds after the epoch, and at UTC around it
+fn tzif() -> Vec<u8> {
+    let mut data = b"TZif".to_vec();
+    data.extend([0; 16]);
+    for count in [0u32, 0, 0, 2, 2, 8]
+    {
+        data.extend(count.to_be_bytes());
+    }
+    data.extend(0i32.to_be_bytes());
+    data.extend(3600i32.to_be_bytes());
+    data.extend([1, 0]);
+    data.extend(0i32.to_be_bytes());
+    data.extend([0, 0]);
+    data.extend(3600i32.to_be_bytes());
+    data.extend([0, 4]);
+    data.extend(b"UTC\0CET\0");
+    data
+}
+
+//
+// Date
+//
+
+#[test]
+fn test_date_days_since_epoch() {
+    assert_eq!(date(1970, 1, 1).days_since_epoch(), 0);
+    assert_eq!(date(2000, 3, 1).days_since_epoch(), 11_017);
+    assert_eq!(date(1969, 12, 31).days_since_epoch(), -1);
+    assert_eq!(Date::from_days_since_epoch(19_783), date(2024, 3, 1));
+}
+
+#[test]
+fn test_date_round_trip() {
+    for days in (-800_000..800_000).step_by(997)
+    {
+        assert_eq!(Date::from_days_since_epoch(days).days_since_epoch(), days);
+    }
+}
+
+#[test]
+fn test_invalid_date() {
+    assert_eq!(Date::new(2023, 2, 29), None);
+    assert_eq!(Date::new(1900, 2, 29), None);
+    assert_eq!(Date::new(2024, 13, 1), None);
+    assert_eq!(Date::new(2024, 4, 31), None);
+    assert!(Date::new(2000, 2, 29).is_some());
+}
+
+#[test]
+fn test_weekday_and_add_days() {
+    assert_eq!(date(1970, 1, 1).weekday(), Weekday::Thursday);
+    assert_eq!(date(2024, 3, 31).weekday(), Weekday::Sunday);
+    assert_eq!(date(2024, 2, 28).add_days(2), date(2024, 3, 1));
+    assert_eq!(date(2024, 1, 1).add_days(-1).to_string(), "2023-12-31");
+}
+
+//
+// Fixed and POSIX zones
+//
+
+#[test]
+fn test_fixed_zone() {
+    let zone = Zone::fixed(Duration::from_minutes(330));
+    assert_eq!(zone.name(), "+05:30");
+    assert_eq!(ZonedClock::from_timestamp(0, &zone).to_string(), "1970-01-01 05:30+05:30");
+    assert_eq!(Zone::fixed(Duration::from_hours(-3)).name(), "-03:00");
+}
+
+#[test]
+fn test_utc() {
+    let zoned = ZonedClock::new(date(2024, 6, 1), Clock::new(12, 0), &Zone::utc());
+    assert_eq!(zoned.timestamp(), 1_717_243_200_000);
+    assert_eq!(zoned.to_string(), "2024-06-01 12:00+00:00");
+}
+
+#[test]
+fn test_posix_zone() {
+    let zone = Zone::posix("EST5EDT").unwrap();
+    assert_eq!(zone.name(), "EST5EDT");
+    assert_eq!(ZonedClock::new(date(2024, 1, 15), Clock::new(12, 0), &zone).offset(), Duration::from_hours(-5));
+    assert_eq!(ZonedClock::new(date(2024, 7, 15), Clock::new(12, 0), &zone).offset(), Duration::from_hours(-4));
+}
+
+#[test]
+fn test_posix_julian_days() {
+    let zone = Zone::posix("AAA-1BBB,J60/0,J300/0").unwrap();
+    // J60 is the 1st of March, even in a leap year
+    assert_eq!(zone.resolve(date(2024, 2, 29), &Clock::new(12, 0)), LocalResult::Single(Duration::from_hours(1)));
+    assert_eq!(zone.resolve(date(2024, 3, 1), &Clock::new(12, 0)), LocalResult::Single(Duration::from_hours(2)));
+}
+
+#[test]
+fn test_invalid_posix_rule() {
+    assert_eq!(Zone::posix("X1"), Err(ZoneError::InvalidRule));
+    assert_eq!(Zone::posix("CET-1CEST,M3.5.0"), Err(ZoneError::InvalidRule));
+    assert_eq!(Zone::posix("CET-1CEST,M13.5.0,M10.5.0"), Err(ZoneError::InvalidRule));
+    assert!(Zone::posix("<+0330>-3:30").is_ok());
+}
+
+#[test]
+fn test_unknown_zone() {
+    assert_eq!(Zone::embedded("Mars/Olympus_Mons"), Err(ZoneError::UnknownZone));
+    assert_eq!(Zone::from_tzdata("../../etc/passwd"), Err(ZoneError::UnknownZone));
+    assert_eq!(Zone::named("Mars/Olympus_Mons"), Err(ZoneError::UnknownZone));
+}
+
+//
+// Daylight saving time
+//
+
+#[test]
+fn test_skipped_hour_moves_forward() {
+    assert_eq!(zoned("Europe/Paris", 2024, 3, 31, 2, 30), "2024-03-31 03:30+02:00");
+    assert_eq!(zoned("America/New_York", 2024, 3, 10, 2, 30), "2024-03-10 03:30-04:00");
+}
+
+#[test]
+fn test_skipped_hour_resolves_as_skipped() {
+    let zone = Zone::embedded("Europe/Paris").unwrap();
+    assert_eq!(zone.resolve(date(2024, 3, 31), &Clock::new(2, 30)), LocalResult::Skipped(Duration::from_hours(1)));
+}
+
+#[test]
+fn test_repeated_hour_takes_the_first() {
+    assert_eq!(zoned("Europe/Paris", 2024, 10, 27, 2, 30), "2024-10-27 02:30+02:00");
+    assert_eq!(zoned("America/New_York", 2024, 11, 3, 1, 30), "2024-11-03 01:30-04:00");
+}
+
+#[test]
+fn test_repeated_hour_resolves_as_ambiguous() {
+    let zone = Zone::embedded("America/New_York").unwrap();
+    match ZonedClock::resolve(date(2024, 11, 3), Clock::new(1, 30), &zone)
+    {
+        LocalResult::Ambiguous(earlier, later) => {
+            assert_eq!(earlier.to_string(), "2024-11-03 01:30-04:00");
+            assert_eq!(later.to_string(), "2024-11-03 01:30-05:00");
+            assert_eq!(later.timestamp() - earlier.timestamp(), 3_600_000);
+        }
+        result => panic!("{:?}", result),
+    }
+}
+
+#[test]
+fn test_around_transitions() {
+    let zone = Zone::embedded("Europe/Paris").unwrap();
+    assert_eq!(zone.resolve(date(2024, 3, 31), &Clock::new(1, 59)), LocalResult::Single(Duration::from_hours(1)));
+    assert_eq!(zone.resolve(date(2024, 3, 31), &Clock::new(3, 0)), LocalResult::Single(Duration::from_hours(2)));
+    assert_eq!(zone.resolve(date(2024, 10, 27), &Clock::new(1, 59)), LocalResult::Single(Duration::from_hours(2)));
+    assert_eq!(zone.resolve(date(2024, 10, 27), &Clock::new(3, 0)), LocalResult::Single(Duration::from_hours(1)));
+}
+
+#[test]
+fn test_southern_hemisphere() {
+    assert_eq!(zoned("Australia/Sydney", 2024, 1, 15, 12, 0), "2024-01-15 12:00+11:00");
+    assert_eq!(zoned("Australia/Sydney", 2024, 7, 15, 12, 0), "2024-07-15 12:00+10:00");
+    assert_eq!(zoned("Australia/Sydney", 2024, 10, 6, 2, 30), "2024-10-06 03:30+11:00");
+    assert_eq!(zoned("Australia/Sydney", 2024, 4, 7, 2, 30), "2024-04-07 02:30+11:00");
+}
+
+#[test]
+fn test_london_transitions_at_one_utc() {
+    assert_eq!(zoned("Europe/London", 2024, 3, 31, 1, 30), "2024-03-31 02:30+01:00");
+    assert_eq!(zoned("Europe/London", 2024, 10, 27, 1, 30), "2024-10-27 01:30+01:00");
+}
+
+//
+// Conversions
+//
+
+#[test]
+fn test_to_zone() {
+    let paris = ZonedClock::new(date(2024, 7, 1), Clock::new(12, 0), &Zone::embedded("Europe/Paris").unwrap());
+    assert_eq!(paris.to_zone(&Zone::embedded("America/New_York").unwrap()).to_string(), "2024-07-01 06:00-04:00");
+    assert_eq!(paris.to_zone(&Zone::embedded("Asia/Tokyo").unwrap()).to_string(), "2024-07-01 19:00+09:00");
+    assert_eq!(paris.to_zone(&Zone::embedded("Asia/Kolkata").unwrap()).to_string(), "2024-07-01 15:30+05:30");
+    assert_eq!(paris.to_zone(&Zone::utc()).timestamp(), paris.timestamp());
+}
+
+#[test]
+fn test_to_zone_changes_the_date() {
+    let auckland = ZonedClock::new(date(2024, 1, 1), Clock::new(9, 0), &Zone::embedded("Pacific/Auckland").unwrap());
+    assert_eq!(auckland.to_zone(&Zone::embedded("America/Los_Angeles").unwrap()).to_string(), "2023-12-31 12:00-08:00");
+}
+
+#[test]
+fn test_before_the_epoch() {
+    let zoned = ZonedClock::from_timestamp(-1, &Zone::embedded("America/New_York").unwrap());
+    assert_eq!(zoned.date(), date(1969, 12, 31));
+    assert_eq!(zoned.clock(), &Clock::with_milliseconds(18, 59, 59, 999));
+    assert_eq!(zoned.timestamp(), -1);
+}
+
+#[test]
+fn test_timestamp_round_trip() {
+    let zone = Zone::embedded("Europe/Berlin").unwrap();
+    for timestamp in (1_700_000_000_000..1_740_000_000_000i64).step_by(3_599_999_937)
+    {
+        assert_eq!(ZonedClock::from_timestamp(timestamp, &zone).timestamp(), timestamp);
+    }
+}
+
+//
+// TZif
+//
+
+#[test]
+fn test_tzif() {
+    let zone = Zone::from_tzif("Test", &tzif()).unwrap();
+    assert_eq!(zone.name(), "Test");
+    assert_eq!(zone.offset_at(-1), Duration::from_hours(0));
+    assert_eq!(zone.offset_at(0), Duration::from_hours(1));
+    assert_eq!(zone.offset_at(3_599_999), Duration::from_hours(1));
+    assert_eq!(zone.offset_at(3_600_000), Duration::from_hours(0));
+}
+
+#[test]
+fn test_invalid_tzif() {
+    let data = tzif();
+    assert_eq!(Zone::from_tzif("Test", &data[..data.len() - 1]), Err(ZoneError::InvalidData));
+    assert_eq!(Zone::from_tzif("Test", b"not a tzdata file"), Err(ZoneError::InvalidData));
+}
+
+#[test]
+fn test_system_tzdata() {
+    // skipped where there is no tzdata
+    let zone = match Zone::from_tzdata("Europe/Paris")
+    {
+        Ok(zone) => zone,
+        Err(_) => return,
+    };
+
+    // France had no daylight saving time in 1970, which the embedded rules do not know
+    let summer_1970 = ZonedClock::new(date(1970, 7, 1), Clock::new(12, 0), &zone);
+    assert_eq!(summer_1970.offset(), Duration::from_hours(1));
+    // past the last transition, the rule of the footer applies
+    assert_eq!(zoned("Europe/Paris", 2100, 3, 28, 2, 30), ZonedClock::new(date(2100, 3, 28), Clock::new(2, 30), &zone).to_string());
+    assert_eq!(ZonedClock::new(date(2100, 7, 1), Clock::new(12, 0), &zone).offset(), Duration::from_hours(2));
+}
@@ -0,0 +1,63 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use poker::{fast_rank, Deck, PokerCard, PokerHand, RuleSet};
+
+fn ranking(c: &mut Criterion) {
+    let hands: Vec<PokerHand> = (0..100)
+        .map(|seed| {
+            PokerHand::new(
+                Deck::shuffled(seed)
+                    .deal_many(5)
+                    .unwrap()
+                    .try_into()
+                    .unwrap(),
+            )
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("five card ranking");
+
+    group.bench_function("hand combos", |b| {
+        b.iter(|| {
+            for hand in &hands {
+                black_box(black_box(hand).combos());
+            }
+        })
+    });
+
+    group.bench_function("lookup tables", |b| {
+        b.iter(|| {
+            for hand in &hands {
+                black_box(fast_rank(black_box(hand).cards()));
+            }
+        })
+    });
+
+    group.finish();
+
+    let sets: Vec<Vec<PokerCard>> = (0..100)
+        .map(|seed| Deck::shuffled(seed).deal_many(7).unwrap())
+        .collect();
+
+    let mut group = c.benchmark_group("seven card ranking");
+
+    group.bench_function("hand combos", |b| {
+        b.iter(|| {
+            for cards in &sets {
+                black_box(RuleSet::High.best_combos(black_box(cards), &[]));
+            }
+        })
+    });
+
+    group.bench_function("lookup tables", |b| {
+        b.iter(|| {
+            for cards in &sets {
+                black_box(fast_rank(black_box(cards)));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, ranking);
+criterion_main!(benches);
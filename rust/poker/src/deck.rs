@@ -0,0 +1,83 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::PokerCard;
+
+/// A deck of cards, dealt from the top.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Deck {
+    cards: Vec<PokerCard>, // the top of the deck is the end of the vector
+}
+
+impl Deck {
+    /// A standard 52 cards deck, sorted by color then by value.
+    pub fn new() -> Self {
+        let mut cards = PokerCard::deck();
+        cards.reverse(); // so that the first card of the standard deck is the first one dealt
+
+        Deck { cards }
+    }
+
+    /// A standard 52 cards deck, shuffled with the given seed: the same seed always gives the same deck.
+    pub fn shuffled(seed: u64) -> Self {
+        let mut deck = Deck::new();
+        deck.shuffle(seed);
+        deck
+    }
+
+    /// Shuffles the cards remaining in the deck.
+    pub fn shuffle(&mut self, seed: u64) {
+        self.cards.shuffle(&mut StdRng::seed_from_u64(seed));
+    }
+
+    /// Deals the card on top of the deck, if there is one left.
+    pub fn deal(&mut self) -> Option<PokerCard> {
+        self.cards.pop()
+    }
+
+    /// Deals `amount` cards from the top of the deck, or none if there are not enough cards left.
+    pub fn deal_many(&mut self, amount: usize) -> Option<Vec<PokerCard>> {
+        if amount > self.cards.len() {
+            return None;
+        }
+
+        Some(
+            self.cards
+                .split_off(self.cards.len() - amount)
+                .into_iter()
+                .rev()
+                .collect(),
+        )
+    }
+
+    /// Takes the given card out of the deck, returning whether it was in it.
+    pub fn remove(&mut self, card: &PokerCard) -> bool {
+        match self.cards.iter().position(|other| other == card) {
+            Some(index) => {
+                self.cards.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The cards remaining in the deck, from the next one to be dealt to the last one.
+    pub fn cards(&self) -> impl Iterator<Item = &PokerCard> {
+        self.cards.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Self {
+        Deck::new()
+    }
+}
@@ -0,0 +1,167 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use crate::fast::fast_rank;
+use crate::holdem::{combinations, parse_cards};
+use crate::PokerCard;
+
+const BOARD_SIZE: usize = 5;
+
+/// How the boards which are still to come are generated.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EquityMethod {
+    /// Every possible board is dealt exactly once.
+    Exhaustive,
+    /// `samples` random boards are dealt, the same `seed` always giving the same boards.
+    MonteCarlo { samples: usize, seed: u64 },
+}
+
+/// How often a player wins, ties or loses, as percentages of the dealt boards.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Equity {
+    pub win: f64,
+    pub tie: f64,
+    pub loss: f64,
+}
+
+/// Given the hole cards of every player and the cards already on the board (possibly none),
+/// compute the equity of each player by dealing the rest of the board.
+///
+/// The equities are returned in the same order as the players.
+/// Returns `None` if any of the cards cannot be parsed, if a card is known twice,
+/// or if there are more than five cards on the board.
+pub fn equity(hole_cards: &[&str], board: &str, method: EquityMethod) -> Option<Vec<Equity>> {
+    let players = hole_cards
+        .iter()
+        .map(|hand| parse_cards(hand).ok())
+        .collect::<Option<Vec<Vec<PokerCard>>>>()?;
+    let board = parse_cards(board).ok()?;
+
+    cards_equity(&players, &board, method)
+}
+
+// the equity of players whose cards were already parsed
+pub(crate) fn cards_equity(
+    players: &[Vec<PokerCard>],
+    board: &[PokerCard],
+    method: EquityMethod,
+) -> Option<Vec<Equity>> {
+    if board.len() > BOARD_SIZE {
+        return None;
+    }
+
+    let known_cards: Vec<&PokerCard> = players.iter().flatten().chain(board.iter()).collect();
+    for (i, card) in known_cards.iter().enumerate() {
+        if known_cards[i + 1..].contains(card) {
+            return None;
+        }
+    }
+
+    let deck: Vec<PokerCard> = PokerCard::deck()
+        .into_iter()
+        .filter(|card| !known_cards.contains(&card))
+        .collect();
+    let missing_cards = BOARD_SIZE - board.len();
+
+    let mut tally = Tally::new(players.len());
+
+    match method {
+        EquityMethod::Exhaustive => {
+            for indexes in combinations(deck.len(), missing_cards) {
+                let mut full_board = board.to_vec();
+                full_board.extend(indexes.iter().map(|&i| deck[i].clone()));
+
+                tally.record(players, &full_board);
+            }
+        }
+        EquityMethod::MonteCarlo { samples, seed } => {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut deck = deck;
+
+            for _ in 0..samples {
+                let (dealt, _) = deck.partial_shuffle(&mut rng, missing_cards);
+                let mut full_board = board.to_vec();
+                full_board.extend(dealt.iter().cloned());
+
+                tally.record(players, &full_board);
+            }
+        }
+    }
+
+    Some(tally.into_equities())
+}
+
+// the amount of boards won, tied and lost by every player
+struct Tally {
+    boards: usize,
+    wins: Vec<usize>,
+    ties: Vec<usize>,
+}
+
+impl Tally {
+    fn new(players: usize) -> Self {
+        Tally {
+            boards: 0,
+            wins: vec![0; players],
+            ties: vec![0; players],
+        }
+    }
+
+    fn record(&mut self, players: &[Vec<PokerCard>], board: &[PokerCard]) {
+        let ranks: Vec<Option<u16>> = players
+            .iter()
+            .map(|hole_cards| {
+                let mut cards = hole_cards.clone();
+                cards.extend(board.iter().cloned());
+
+                fast_rank(&cards)
+            })
+            .collect();
+
+        let best_rank = ranks.iter().flatten().max();
+        let winners: Vec<usize> = (0..players.len())
+            .filter(|&i| ranks[i].is_some() && ranks[i].as_ref() == best_rank)
+            .collect();
+
+        match winners.as_slice() {
+            [winner] => self.wins[*winner] += 1,
+            _ => winners.iter().for_each(|&i| self.ties[i] += 1),
+        }
+        self.boards += 1;
+    }
+
+    fn into_equities(self) -> Vec<Equity> {
+        if self.boards == 0 {
+            return vec![Equity::default(); self.wins.len()];
+        }
+
+        let percentage = |amount: usize| amount as f64 * 100.0 / self.boards as f64;
+
+        self.wins
+            .iter()
+            .zip(self.ties.iter())
+            .map(|(&wins, &ties)| Equity {
+                win: percentage(wins),
+                tie: percentage(ties),
+                loss: percentage(self.boards - wins - ties),
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn tally_splits_ties() {
+    let players = vec![
+        parse_cards("2S 3D").unwrap(),
+        parse_cards("2D 3S").unwrap(),
+        parse_cards("4C 5C").unwrap(),
+    ];
+    let mut tally = Tally::new(3);
+
+    tally.record(&players, &parse_cards("AH KH QD JC 3C").unwrap());
+    tally.record(&players, &parse_cards("AH KH QD 5S 9S").unwrap());
+
+    assert_eq!(tally.wins, vec![0, 0, 1]);
+    assert_eq!(tally.ties, vec![1, 1, 0]);
+}
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::holdem::combinations;
+use crate::{Color, HandCombo, PokerCard, PokerHand, Value};
+
+const PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+/// Ranks a set of at least five cards by looking up its best five cards in precomputed tables.
+///
+/// The rank goes from 1 (the worst high card) to 7462 (a royal flush), so comparing the ranks of
+/// two sets of cards gives the same result as comparing their best `HandCombo`s, only much faster.
+/// Returns `None` if there are less than five cards, or if a card is there twice.
+pub fn fast_rank(cards: &[PokerCard]) -> Option<u16> {
+    // a deck has 52 cards, so that more cards hold a card twice
+    if cards.len() > 52 {
+        return None;
+    }
+
+    let tables = Tables::get();
+    let mut encoded = [0; 52];
+    let mut seen: u64 = 0; // a bit for every card of the deck
+
+    for (i, card) in cards.iter().enumerate() {
+        encoded[i] = encode(card);
+
+        let bit =
+            1 << (((encoded[i] >> 8) & 0xF) * 4 + ((encoded[i] >> 12) & 0xF).trailing_zeros());
+        if seen & bit != 0 {
+            return None;
+        }
+        seen |= bit;
+    }
+
+    let encoded = &encoded[..cards.len()];
+    match cards.len() {
+        5..=7 => tables.rank_best(encoded, tables.subsets[cards.len() - 5].iter().copied()),
+        _ => tables.rank_best(
+            encoded,
+            // the unwrap is safe because we asked for 5 indexes
+            combinations(cards.len(), 5).map(|indexes| <[usize; 5]>::try_from(indexes).unwrap()),
+        ),
+    }
+}
+
+// the card is encoded on 29 bits the same way as in Cactus Kev's evaluator:
+//   xxxbbbbb bbbbbbbb chdsrrrr xxpppppp
+// where b is set for the value of the card, chds for its color, r is the index of its value
+// and p is the prime number associated to its value
+fn encode(card: &PokerCard) -> u32 {
+    let value_index = (card.value.ranking() - 2) as u32;
+    // the unwrap is safe because every color is in ALL
+    let color_index = Color::ALL.iter().position(|&c| c == card.color).unwrap() as u32;
+
+    (1 << (16 + value_index))
+        | (1 << (12 + color_index))
+        | (value_index << 8)
+        | PRIMES[value_index as usize]
+}
+
+fn five_card_subsets(cards: usize) -> Vec<[usize; 5]> {
+    combinations(cards, 5)
+        // the unwrap is safe because we asked for 5 indexes
+        .map(|indexes| <[usize; 5]>::try_from(indexes).unwrap())
+        .collect()
+}
+
+struct Tables {
+    flushes: Vec<u16>, // indexed by the value bits of five cards of the same color
+    unique_values: Vec<u16>, // indexed by the value bits of five cards of different values
+    products: HashMap<u32, u16>, // indexed by the product of the primes of the other hands
+    subsets: [Vec<[usize; 5]>; 3], // the ways of picking five cards out of five, six and seven
+}
+
+impl Tables {
+    fn get() -> &'static Tables {
+        static TABLES: OnceLock<Tables> = OnceLock::new();
+        TABLES.get_or_init(Tables::build)
+    }
+
+    // `None` for five cards which can not be in a deck, like five aces
+    fn rank_five(&self, cards: [u32; 5]) -> Option<u16> {
+        let value_bits = (cards.iter().fold(0, |bits, card| bits | card) >> 16) as usize;
+
+        if cards.iter().fold(0xF000, |bits, card| bits & card) != 0 {
+            return Some(self.flushes[value_bits]).filter(|&rank| rank != 0);
+        }
+
+        match self.unique_values[value_bits] {
+            0 => self
+                .products
+                .get(&cards.iter().map(|card| card & 0xFF).product())
+                .copied(),
+            rank => Some(rank),
+        }
+    }
+
+    // the best rank of the five cards picked by each subset, `None` if one of them cannot be ranked
+    fn rank_best(&self, encoded: &[u32], subsets: impl Iterator<Item = [usize; 5]>) -> Option<u16> {
+        subsets
+            .map(|indexes| self.rank_five(indexes.map(|i| encoded[i])))
+            .try_fold(None, |best: Option<u16>, rank| Some(best.max(Some(rank?))))?
+    }
+
+    // every distinct five-card hand is ranked once by the `HandCombo` evaluator, so that both
+    // evaluators always agree
+    fn build() -> Tables {
+        let mut hands: Vec<(Vec<HandCombo>, Vec<PokerCard>)> = value_counts(13, 5)
+            .into_iter()
+            .flat_map(|counts| representative_hands(&counts))
+            .map(|cards| {
+                // the unwrap is safe because representative hands have 5 cards
+                let hand = PokerHand(<[PokerCard; 5]>::try_from(cards.clone()).unwrap());
+                (hand.combos(), cards)
+            })
+            .collect();
+
+        hands.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+
+        let mut tables = Tables {
+            flushes: vec![0; 1 << 13],
+            unique_values: vec![0; 1 << 13],
+            products: HashMap::new(),
+            subsets: [
+                five_card_subsets(5),
+                five_card_subsets(6),
+                five_card_subsets(7),
+            ],
+        };
+
+        let mut rank = 0;
+        for (i, (combos, cards)) in hands.iter().enumerate() {
+            if i == 0 || Some(combos) != hands.get(i - 1).map(|(combos, _)| combos) {
+                rank += 1;
+            }
+
+            let encoded: Vec<u32> = cards.iter().map(encode).collect();
+            let value_bits = (encoded.iter().fold(0, |bits, card| bits | card) >> 16) as usize;
+
+            let different_values = value_bits.count_ones() == 5;
+            let same_color = cards.iter().all(|card| card.color == cards[0].color);
+
+            if different_values && same_color {
+                tables.flushes[value_bits] = rank;
+            } else if different_values {
+                tables.unique_values[value_bits] = rank;
+            } else {
+                tables
+                    .products
+                    .insert(encoded.iter().map(|card| card & 0xFF).product(), rank);
+            }
+        }
+
+        tables
+    }
+}
+
+// every way of having `cards` cards among `values` values, with at most four cards per value
+fn value_counts(values: usize, cards: usize) -> Vec<Vec<usize>> {
+    if values == 0 {
+        return if cards == 0 { vec![vec![]] } else { vec![] };
+    }
+
+    (0..=cards.min(4))
+        .flat_map(|count| {
+            value_counts(values - 1, cards - count)
+                .into_iter()
+                .map(move |mut counts| {
+                    counts.push(count);
+                    counts
+                })
+        })
+        .collect()
+}
+
+// a hand for every way of coloring the given value counts which can change its rank:
+// hands with five different values can be a flush or not
+fn representative_hands(counts: &[usize]) -> Vec<Vec<PokerCard>> {
+    let values: Vec<Value> = counts
+        .iter()
+        .enumerate()
+        .flat_map(|(value_index, &count)| vec![Value::ALL[value_index]; count])
+        .collect();
+
+    let different_values = counts.iter().all(|&count| count <= 1);
+
+    let mut offsuit: Vec<PokerCard> = vec![];
+    for (i, &value) in values.iter().enumerate() {
+        // five different values are never all of the same color,
+        // and cards of the same value never share a color
+        let color_index = match different_values {
+            true => i % 4,
+            false => values[..i].iter().filter(|&&other| other == value).count(),
+        };
+
+        offsuit.push(PokerCard::new(value, Color::ALL[color_index]));
+    }
+
+    if different_values {
+        let suited = values
+            .iter()
+            .map(|&value| PokerCard::new(value, Color::Spade))
+            .collect();
+        vec![offsuit, suited]
+    } else {
+        vec![offsuit]
+    }
+}
+
+#[test]
+fn every_distinct_hand_is_ranked() {
+    let tables = Tables::get();
+
+    assert_eq!(
+        tables.flushes.iter().filter(|&&rank| rank != 0).count()
+            + tables
+                .unique_values
+                .iter()
+                .filter(|&&rank| rank != 0)
+                .count()
+            + tables.products.len(),
+        7462
+    );
+    assert_eq!(tables.flushes.iter().max(), Some(&7462));
+}
+
+#[test]
+fn encoded_card() {
+    let king_of_diamonds = PokerCard::new(Value::King, Color::Diamond);
+
+    assert_eq!(
+        encode(&king_of_diamonds),
+        0b00001000_00000000_00101011_00100101
+    );
+}
@@ -0,0 +1,430 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::holdem::{parse_cards, BestHand};
+use crate::pot::side_pots;
+use crate::{HandCombo, PokerCard, PokerHandFromStrConversionError};
+
+/// A hand of Texas Hold'em as written in a session log, for example:
+///
+/// ```text
+/// Seat 1: alice (1000)
+/// Seat 2: bob (1000)
+/// Dealt to alice: AS KD
+/// *** PREFLOP ***
+/// alice posts small blind 5
+/// bob posts big blind 10
+/// alice raises to 30
+/// bob calls 20
+/// *** FLOP *** 9H 2S 7C
+/// bob checks
+/// alice bets 50
+/// bob calls 50
+/// *** TURN *** KS
+/// bob checks
+/// alice checks
+/// *** RIVER *** 3D
+/// bob bets 100
+/// alice folds
+/// ```
+///
+/// The amounts of `calls`, `bets` and `posts` are the chips the player adds,
+/// while `raises to` gives the total the player has bet on the street.
+/// An optional `*** SHOWDOWN ***` section lists the hole cards of the players, like `bob shows 9C 9D`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct HandHistory {
+    pub seats: Vec<Seat>,
+    pub dealt: Vec<(String, Vec<PokerCard>)>,
+    pub streets: Vec<StreetActions>,
+    pub shown: Vec<(String, Vec<PokerCard>)>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Seat {
+    pub number: u8,
+    pub player: String,
+    pub stack: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+/// The cards dealt on the board when a street starts, and what the players did during it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StreetActions {
+    pub street: Street,
+    pub board: Vec<PokerCard>,
+    pub actions: Vec<PlayerAction>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PlayerAction {
+    pub line: usize,
+    pub player: String,
+    pub action: Action,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    Post(u32),
+    Fold,
+    Check,
+    Call(u32),
+    Bet(u32),
+    RaiseTo(u32),
+}
+
+/// The reasons why a hand history could not be parsed or replayed, with the line at fault
+/// (starting at 1).
+#[derive(PartialEq, Debug)]
+pub enum HandHistoryError {
+    InvalidLine(usize),
+    InvalidCard(usize, PokerHandFromStrConversionError),
+    DuplicateSeat(usize),
+    UnknownPlayer(usize, String),
+    ActionBeforePreflop(usize),
+    FoldedPlayerActed(usize, String),
+    NotEnoughChips(usize, String),
+    /// A player raised to no more than the bet of the street.
+    RaiseTooSmall(usize, String),
+    NoSeats,
+    /// A pot went to showdown without the cards of any of its players.
+    UnknownWinner,
+}
+
+/// What happened to the chips at the end of a hand.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Replay {
+    pub pots: Vec<PotResult>,       // the main pot first, then the side pots
+    pub stacks: Vec<(String, u32)>, // the stacks at the end of the hand, in seat order
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PotResult {
+    pub amount: u32,
+    pub eligible: Vec<String>,
+    pub winners: Vec<String>,
+}
+
+impl fmt::Display for HandHistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandHistoryError::InvalidLine(line) => write!(f, "line {}: cannot be parsed", line),
+            HandHistoryError::InvalidCard(line, error) => write!(f, "line {}: {}", line, error),
+            HandHistoryError::DuplicateSeat(line) => write!(f, "line {}: seat already taken", line),
+            HandHistoryError::UnknownPlayer(line, player) => {
+                write!(f, "line {}: {} is not seated", line, player)
+            }
+            HandHistoryError::ActionBeforePreflop(line) => {
+                write!(f, "line {}: action before the preflop", line)
+            }
+            HandHistoryError::FoldedPlayerActed(line, player) => {
+                write!(f, "line {}: {} acted after folding", line, player)
+            }
+            HandHistoryError::NotEnoughChips(line, player) => {
+                write!(f, "line {}: {} does not have enough chips", line, player)
+            }
+            HandHistoryError::RaiseTooSmall(line, player) => {
+                write!(f, "line {}: {} does not raise above the bet", line, player)
+            }
+            HandHistoryError::NoSeats => write!(f, "nobody is seated"),
+            HandHistoryError::UnknownWinner => write!(f, "no cards were shown for a pot"),
+        }
+    }
+}
+
+impl Error for HandHistoryError {}
+
+impl FromStr for HandHistory {
+    type Err = HandHistoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut history = HandHistory {
+            seats: vec![],
+            dealt: vec![],
+            streets: vec![],
+            shown: vec![],
+        };
+        let mut showdown = false;
+
+        for (i, line) in s.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.trim();
+            let words: Vec<&str> = line.split_whitespace().collect();
+
+            let cards = |text: &str| {
+                parse_cards(text).map_err(|error| HandHistoryError::InvalidCard(line_number, error))
+            };
+
+            match words.as_slice() {
+                [] => {}
+                ["Seat", number, player, stack] => {
+                    let seat = Seat {
+                        number: parse_number(number.strip_suffix(':'), line_number)?,
+                        player: player.to_string(),
+                        stack: parse_number(
+                            stack.strip_prefix('(').and_then(|s| s.strip_suffix(')')),
+                            line_number,
+                        )?,
+                    };
+
+                    if history
+                        .seats
+                        .iter()
+                        .any(|other| other.number == seat.number || other.player == seat.player)
+                    {
+                        return Err(HandHistoryError::DuplicateSeat(line_number));
+                    }
+                    history.seats.push(seat);
+                }
+                ["Dealt", "to", player, ..] => {
+                    let player = player
+                        .strip_suffix(':')
+                        .ok_or(HandHistoryError::InvalidLine(line_number))?;
+                    history
+                        .dealt
+                        .push((player.to_string(), cards(&words[3..].join(" "))?));
+                }
+                ["***", "SHOWDOWN", "***"] => showdown = true,
+                ["***", street, "***", board @ ..] => {
+                    let street = match *street {
+                        "PREFLOP" => Street::Preflop,
+                        "FLOP" => Street::Flop,
+                        "TURN" => Street::Turn,
+                        "RIVER" => Street::River,
+                        _ => return Err(HandHistoryError::InvalidLine(line_number)),
+                    };
+
+                    history.streets.push(StreetActions {
+                        street,
+                        board: cards(&board.join(" "))?,
+                        actions: vec![],
+                    });
+                }
+                [player, "shows", ..] if showdown => {
+                    history
+                        .shown
+                        .push((player.to_string(), cards(&words[2..].join(" "))?));
+                }
+                [player, action @ ..] => {
+                    let action = match action {
+                        ["posts", "small", "blind", amount]
+                        | ["posts", "big", "blind", amount]
+                        | ["posts", amount] => {
+                            Action::Post(parse_number(Some(amount), line_number)?)
+                        }
+                        ["folds"] => Action::Fold,
+                        ["checks"] => Action::Check,
+                        ["calls", amount] => Action::Call(parse_number(Some(amount), line_number)?),
+                        ["bets", amount] => Action::Bet(parse_number(Some(amount), line_number)?),
+                        ["raises", "to", amount] => {
+                            Action::RaiseTo(parse_number(Some(amount), line_number)?)
+                        }
+                        _ => return Err(HandHistoryError::InvalidLine(line_number)),
+                    };
+
+                    history
+                        .streets
+                        .last_mut()
+                        .ok_or(HandHistoryError::ActionBeforePreflop(line_number))?
+                        .actions
+                        .push(PlayerAction {
+                            line: line_number,
+                            player: player.to_string(),
+                            action,
+                        });
+                }
+            }
+        }
+
+        if history.seats.is_empty() {
+            return Err(HandHistoryError::NoSeats);
+        }
+
+        Ok(history)
+    }
+}
+
+fn parse_number<T: FromStr>(s: Option<&str>, line: usize) -> Result<T, HandHistoryError> {
+    s.and_then(|s| s.parse().ok())
+        .ok_or(HandHistoryError::InvalidLine(line))
+}
+
+impl HandHistory {
+    /// The cards on the board at the end of the hand.
+    pub fn board(&self) -> Vec<PokerCard> {
+        self.streets
+            .iter()
+            .flat_map(|street| street.board.iter().cloned())
+            .collect()
+    }
+
+    /// Replays the betting to rebuild the pots, and gives them to the players with the best hands.
+    ///
+    /// A player who is still in the hand at the end can only win a pot if their hole cards are known,
+    /// either because they were dealt to them or because they showed them.
+    pub fn replay(&self) -> Result<Replay, HandHistoryError> {
+        let mut stacks: Vec<u32> = self.seats.iter().map(|seat| seat.stack).collect();
+        let mut contributions: Vec<u32> = vec![0; self.seats.len()];
+        let mut folded: Vec<bool> = vec![false; self.seats.len()];
+
+        for street in &self.streets {
+            let mut street_bets: Vec<u32> = vec![0; self.seats.len()];
+
+            for action in &street.actions {
+                let seat = self.seat_index(&action.player).ok_or_else(|| {
+                    HandHistoryError::UnknownPlayer(action.line, action.player.clone())
+                })?;
+
+                if folded[seat] {
+                    return Err(HandHistoryError::FoldedPlayerActed(
+                        action.line,
+                        action.player.clone(),
+                    ));
+                }
+
+                let amount = match action.action {
+                    Action::Fold => {
+                        folded[seat] = true;
+                        0
+                    }
+                    Action::Check => 0,
+                    Action::Post(amount) | Action::Call(amount) | Action::Bet(amount) => amount,
+                    Action::RaiseTo(total) => {
+                        // the unwrap is safe because the player who raises is seated
+                        if total <= *street_bets.iter().max().unwrap() {
+                            return Err(HandHistoryError::RaiseTooSmall(
+                                action.line,
+                                action.player.clone(),
+                            ));
+                        }
+                        total - street_bets[seat]
+                    }
+                };
+
+                if amount > stacks[seat] {
+                    return Err(HandHistoryError::NotEnoughChips(
+                        action.line,
+                        action.player.clone(),
+                    ));
+                }
+
+                stacks[seat] -= amount;
+                street_bets[seat] += amount;
+                contributions[seat] += amount;
+            }
+        }
+
+        let board = self.board();
+        let combos: Vec<Option<Vec<HandCombo>>> = (0..self.seats.len())
+            .map(|seat| {
+                let mut cards = self.hole_cards(seat)?;
+                cards.extend(board.iter().cloned());
+
+                BestHand::find(&cards).map(BestHand::into_combos)
+            })
+            .collect();
+
+        let players_left = folded.iter().filter(|&&folded| !folded).count();
+
+        let mut pots: Vec<PotResult> = vec![];
+        for pot in side_pots(&contributions, &folded) {
+            let winners: Vec<usize> = match (players_left, pot.eligible.len()) {
+                // nobody has to show their cards to win an uncontested pot
+                (1, _) | (_, 1) => pot.eligible.clone(),
+                _ => best_players(&pot.eligible, &combos),
+            };
+            if winners.is_empty() {
+                return Err(HandHistoryError::UnknownWinner);
+            }
+
+            for (seat, amount) in pot.split(&winners) {
+                stacks[seat] += amount;
+            }
+
+            pots.push(PotResult {
+                amount: pot.amount,
+                eligible: pot.eligible.iter().map(|&seat| self.player(seat)).collect(),
+                winners: winners.iter().map(|&seat| self.player(seat)).collect(),
+            });
+        }
+
+        Ok(Replay {
+            pots,
+            stacks: (0..self.seats.len())
+                .map(|seat| (self.player(seat), stacks[seat]))
+                .collect(),
+        })
+    }
+
+    fn seat_index(&self, player: &str) -> Option<usize> {
+        self.seats.iter().position(|seat| seat.player == player)
+    }
+
+    fn player(&self, seat: usize) -> String {
+        self.seats[seat].player.clone()
+    }
+
+    fn hole_cards(&self, seat: usize) -> Option<Vec<PokerCard>> {
+        let player = &self.seats[seat].player;
+
+        self.shown
+            .iter()
+            .chain(self.dealt.iter())
+            .find(|(other, _)| other == player)
+            .map(|(_, cards)| cards.clone())
+    }
+}
+
+fn best_players(eligible: &[usize], combos: &[Option<Vec<HandCombo>>]) -> Vec<usize> {
+    let mut best_combos: Option<&Vec<HandCombo>> = None;
+    for combos in eligible.iter().filter_map(|&seat| combos[seat].as_ref()) {
+        if best_combos.is_none_or(|best_combos| combos > best_combos) {
+            best_combos = Some(combos);
+        }
+    }
+
+    eligible
+        .iter()
+        .copied()
+        .filter(|&seat| best_combos.is_some() && combos[seat].as_ref() == best_combos)
+        .collect()
+}
+
+impl Replay {
+    /// The total amount of chips which were in the pots.
+    pub fn total_pot(&self) -> u32 {
+        self.pots.iter().map(|pot| pot.amount).sum()
+    }
+}
+
+#[test]
+fn parse_action_lines() {
+    let history: HandHistory =
+        "Seat 1: alice (100)\n*** PREFLOP ***\nalice posts big blind 10\nalice raises to 40"
+            .parse()
+            .unwrap();
+
+    assert_eq!(
+        history.streets[0]
+            .actions
+            .iter()
+            .map(|action| action.action)
+            .collect::<Vec<Action>>(),
+        vec![Action::Post(10), Action::RaiseTo(40)]
+    );
+}
+
+#[test]
+fn parse_action_before_preflop() {
+    assert_eq!(
+        "Seat 1: alice (100)\nalice checks".parse::<HandHistory>(),
+        Err(HandHistoryError::ActionBeforePreflop(2))
+    );
+}
@@ -0,0 +1,192 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use crate::{
+    winners_by_combos, CardPosition, HandCombo, PokerCard, PokerHand,
+    PokerHandFromStrConversionError,
+};
+
+/// The best five cards which can be picked out of a bigger set of cards.
+#[derive(Debug)]
+pub(crate) struct BestHand {
+    indexes: [usize; 5], // the indexes of the picked cards in the original set
+    combos: Vec<HandCombo>,
+}
+
+impl BestHand {
+    /// Tries every way of picking five cards out of `cards` and keeps the best one.
+    ///
+    /// Returns `None` if there are less than five cards to pick from.
+    pub(crate) fn find(cards: &[PokerCard]) -> Option<BestHand> {
+        let mut best_hand: Option<BestHand> = None;
+
+        for indexes in combinations(cards.len(), 5) {
+            // the unwrap is safe because we asked for 5 indexes
+            let indexes = <[usize; 5]>::try_from(indexes).unwrap();
+            let combos = PokerHand(indexes.map(|i| cards[i].clone())).find_combos();
+
+            let is_better = match &best_hand {
+                Some(best_hand) => combos.partial_cmp(&best_hand.combos) == Some(Ordering::Greater),
+                None => true,
+            };
+
+            if is_better {
+                best_hand = Some(BestHand { indexes, combos });
+            }
+        }
+
+        best_hand
+    }
+
+    pub(crate) fn indexes(&self) -> &[usize; 5] {
+        &self.indexes
+    }
+
+    pub(crate) fn into_combos(self) -> Vec<HandCombo> {
+        self.combos
+    }
+}
+
+/// Every way of picking `k` distinct indexes out of `0..n`, each of them sorted in increasing order.
+///
+/// The picks are made one at a time, in lexicographic order, so that they are never all in memory.
+pub(crate) fn combinations(n: usize, k: usize) -> Combinations {
+    Combinations {
+        n,
+        indexes: (0..k).collect(),
+        done: k > n,
+    }
+}
+
+pub(crate) struct Combinations {
+    n: usize,
+    indexes: Vec<usize>, // the next pick
+    done: bool,
+}
+
+impl Iterator for Combinations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.done {
+            return None;
+        }
+
+        let combination = self.indexes.clone();
+        let (n, k) = (self.n, self.indexes.len());
+
+        // find the rightmost index which can still be moved to the right
+        match (0..k).rev().find(|&i| self.indexes[i] != i + n - k) {
+            Some(position) => {
+                self.indexes[position] += 1;
+                for i in position + 1..k {
+                    self.indexes[i] = self.indexes[i - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+
+        Some(combination)
+    }
+}
+
+// parses cards separated by whitespace, which must all be different
+pub(crate) fn parse_cards(s: &str) -> Result<Vec<PokerCard>, PokerHandFromStrConversionError> {
+    parse_cards_after(s, &[])
+}
+
+// parses cards separated by whitespace, which must all be different from each other and from the
+// cards already dealt, like the board
+pub(crate) fn parse_cards_after(
+    s: &str,
+    dealt: &[PokerCard],
+) -> Result<Vec<PokerCard>, PokerHandFromStrConversionError> {
+    let mut cards: Vec<PokerCard> = vec![];
+
+    for (index, (offset, text)) in card_texts(s).enumerate() {
+        let card = PokerCard::from_str(text).map_err(|error| error.at(index, offset))?;
+
+        if cards.contains(&card) || dealt.contains(&card) {
+            return Err(PokerHandFromStrConversionError::DuplicateCard(
+                CardPosition {
+                    index,
+                    offset,
+                    text: text.to_string(),
+                },
+            ));
+        }
+        cards.push(card);
+    }
+
+    Ok(cards)
+}
+
+// the cards separated by whitespace, with the byte offset at which they start
+pub(crate) fn card_texts(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    // the subtraction is safe because the cards are slices of the str
+    s.split_whitespace()
+        .map(move |text| (text.as_ptr() as usize - s.as_ptr() as usize, text))
+}
+
+/// Given a set of at least five cards (typically the two hole cards and the five community
+/// cards of Texas Hold'em), return the five cards which form the best hand.
+///
+/// Just like `winning_hands`, the returned cards are references to the ones which were passed in.
+/// Returns `None` if the cards cannot be parsed or if there are less than five of them.
+pub fn best_hand(cards: &str) -> Option<Vec<&str>> {
+    let card_strs: Vec<&str> = cards.split_whitespace().collect();
+    let best_hand = BestHand::find(&parse_cards(cards).ok()?)?;
+
+    Some(best_hand.indexes().iter().map(|&i| card_strs[i]).collect())
+}
+
+/// Given the hole cards of every player and the community cards on the board,
+/// return the hole cards of the players which win the showdown.
+///
+/// Every player makes their best five-card hand out of their hole cards and the board.
+/// Players whose cards cannot be parsed, who hold a card of the board, or who do not have five
+/// cards to play with, are not taken into account.
+pub fn winning_holdem_hands<'a>(hole_cards: &[&'a str], board: &str) -> Vec<&'a str> {
+    let board = match parse_cards(board) {
+        Ok(board) => board,
+        Err(_) => return vec![],
+    };
+
+    winners_by_combos(hole_cards.iter().filter_map(|hand| {
+        let mut cards = parse_cards_after(hand, &board).ok()?;
+        cards.extend(board.iter().cloned());
+
+        Some((*hand, BestHand::find(&cards)?.into_combos()))
+    }))
+}
+
+#[test]
+fn combinations_of_seven() {
+    let combinations: Vec<Vec<usize>> = combinations(7, 5).collect();
+
+    assert_eq!(combinations.len(), 21);
+    assert_eq!(combinations.first(), Some(&vec![0, 1, 2, 3, 4]));
+    assert_eq!(combinations.last(), Some(&vec![2, 3, 4, 5, 6]));
+}
+
+#[test]
+fn combinations_too_many_picked() {
+    assert_eq!(combinations(4, 5).next(), None);
+}
+
+#[test]
+fn combinations_of_none_picked() {
+    assert_eq!(combinations(3, 0).collect::<Vec<_>>(), vec![vec![]]);
+}
+
+#[test]
+fn best_hand_of_six_cards() {
+    let cards = parse_cards("2H KS 7D KD 3C KC").unwrap();
+    let best_hand = BestHand::find(&cards).unwrap();
+
+    assert_eq!(best_hand.indexes(), &[1, 2, 3, 4, 5]);
+    assert_eq!(
+        best_hand.into_combos().first(),
+        Some(&HandCombo::ThreeOfAKind(crate::Value::King))
+    );
+}
@@ -1,16 +1,41 @@
 use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
 use std::str::FromStr;
 
 #[macro_use]
 extern crate maplit;
 
-/// Given a list of poker hands, return a list of those hands which win.
-///
-/// Note the type signature: this function should return _the same_ reference to
-/// the winning hand(s) as were passed in, not reconstructed strings which happen to be equal.
-
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Value {
+mod deck;
+mod equity;
+mod fast;
+mod history;
+mod holdem;
+mod pot;
+mod range;
+mod rules;
+mod table;
+mod wild;
+
+use holdem::{card_texts, parse_cards};
+
+pub use deck::Deck;
+pub use equity::{equity, Equity, EquityMethod};
+pub use fast::fast_rank;
+pub use history::{
+    Action, HandHistory, HandHistoryError, PlayerAction, PotResult, Replay, Seat, Street,
+    StreetActions,
+};
+pub use holdem::{best_hand, winning_holdem_hands};
+pub use pot::{side_pots, Pot};
+pub use range::{range_equity, Range, RangeError, WeightedCombo};
+pub use rules::{winning_hands_with_rules, RuleSet};
+pub use table::{HandResult, Table, TableAction, TableError, TablePlayer};
+pub use wild::{winning_wild_hands, WildCard, WildHand, WildRules};
+
+/// The rank of a card, written `2` to `9`, then `T` (or `10`), `J`, `Q`, `K` and `A`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Value {
     Two,
     Three,
     Four,
@@ -26,28 +51,36 @@ enum Value {
     Ace,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-enum Color {
+/// The suit of a card, written `S`, `D`, `H` or `C`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Color {
     Spade,
     Diamond,
     Heart,
     Club,
 }
 
-#[derive(PartialEq, Debug, Clone)]
-struct PokerCard {
+/// A single card, written as its value followed by its color, like `"AC"` or `"7H"`.
+///
+/// Cards are ordered by value first, then by color.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct PokerCard {
     value: Value,
     color: Color,
 }
 
+/// Five cards, written separated by spaces, like `"2S 4S 7H AC JH"`.
 #[derive(PartialEq, Debug, Clone)]
-struct PokerHand([PokerCard; 5]);
+pub struct PokerHand([PokerCard; 5]);
 
 impl FromStr for Value {
     type Err = PokerHandFromStrConversionError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != 1 {
+        if s == "10" {
+            return Ok(Value::Ten);
+        }
+        if s.chars().count() != 1 {
             return Err(PokerHandFromStrConversionError::ValueLenError);
         }
 
@@ -67,10 +100,10 @@ impl FromStr for Value {
             'K' => Value::King,
         };
 
-        match value_chars.get(&s.chars().nth(0).unwrap()) // the unwrap is safe because we know the str length is 1
+        match value_chars.get(&s.chars().next().unwrap().to_ascii_uppercase()) // the unwrap is safe because we know the str has 1 character
         {
             Some(color) => Ok(*color), // won't work without the Copy trait cause we would not be able to move the color out of the HashMap
-            None => Err(PokerHandFromStrConversionError::UnknownValue)
+            None => Err(PokerHandFromStrConversionError::UnknownValue(CardPosition::of(s)))
         }
     }
 }
@@ -79,7 +112,7 @@ impl FromStr for Color {
     type Err = PokerHandFromStrConversionError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != 1 {
+        if s.chars().count() != 1 {
             return Err(PokerHandFromStrConversionError::ColorLenError);
         }
 
@@ -90,10 +123,10 @@ impl FromStr for Color {
             'S' => Color::Spade,
         };
 
-        match color_chars.get(&s.chars().nth(0).unwrap()) // the unwrap is safe because we know the str length is 2
+        match color_chars.get(&s.chars().next().unwrap().to_ascii_uppercase()) // the unwrap is safe because we know the str has 1 character
         {
             Some(color) => Ok(*color), // won't work without the Copy trait cause we would not be able to move the color out of the HashMap
-            None => Err(PokerHandFromStrConversionError::UnknownColor)
+            None => Err(PokerHandFromStrConversionError::UnknownColor(CardPosition::of(s)))
         }
     }
 }
@@ -101,14 +134,26 @@ impl FromStr for Color {
 impl FromStr for PokerCard {
     type Err = PokerHandFromStrConversionError;
 
+    /// Parses a value followed by a color, like `"QH"`. The ten can be written `"T"` or `"10"`,
+    /// and lowercase letters are accepted as well.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != 2 {
-            return Err(PokerHandFromStrConversionError::CardLenError);
+        let position = || CardPosition::of(s);
+
+        let value_len = match s.starts_with("10") {
+            true => 2,
+            false => s.chars().next().map_or(0, char::len_utf8),
+        };
+        let (value, color) = s.split_at(value_len);
+
+        if value.is_empty() || color.chars().count() != 1 {
+            return Err(PokerHandFromStrConversionError::CardLenError(position()));
         }
 
         Ok(PokerCard {
-            value: Value::from_str(s.chars().nth(0).unwrap().to_string().as_str())?,
-            color: Color::from_str(s.chars().nth(1).unwrap().to_string().as_str())?,
+            value: Value::from_str(value)
+                .map_err(|_| PokerHandFromStrConversionError::UnknownValue(position()))?,
+            color: Color::from_str(color)
+                .map_err(|_| PokerHandFromStrConversionError::UnknownColor(position()))?,
         })
     }
 }
@@ -116,22 +161,55 @@ impl FromStr for PokerCard {
 impl FromStr for PokerHand {
     type Err = PokerHandFromStrConversionError;
 
+    /// Parses five cards separated by spaces, which must all be different.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let split_str: Vec<&str> = s.split(" ").collect();
+        let amount = s.split_whitespace().count();
 
-        if split_str.len() != 5 {
-            return Err(PokerHandFromStrConversionError::CardsAmountError);
+        if amount != 5 {
+            return Err(PokerHandFromStrConversionError::CardsAmountError(amount));
         }
 
-        split_str
-            .iter()
-            .map(|str_card_rep| PokerCard::from_str(str_card_rep))
-            .collect::<Result<Vec<PokerCard>, PokerHandFromStrConversionError>>()
-            .map(|vec| PokerHand(<[PokerCard; 5]>::try_from(vec).unwrap()))
+        // the unwrap is safe because we know there are 5 cards
+        parse_cards(s).map(|cards| PokerHand(<[PokerCard; 5]>::try_from(cards).unwrap()))
     }
 }
 
 impl Value {
+    /// Every value, from the lowest to the highest.
+    pub const ALL: [Value; 13] = [
+        Value::Two,
+        Value::Three,
+        Value::Four,
+        Value::Five,
+        Value::Six,
+        Value::Seven,
+        Value::Eight,
+        Value::Nine,
+        Value::Ten,
+        Value::Jack,
+        Value::Queen,
+        Value::King,
+        Value::Ace,
+    ];
+
+    fn symbol(&self) -> char {
+        match self {
+            Value::Two => '2',
+            Value::Three => '3',
+            Value::Four => '4',
+            Value::Five => '5',
+            Value::Six => '6',
+            Value::Seven => '7',
+            Value::Eight => '8',
+            Value::Nine => '9',
+            Value::Ten => 'T',
+            Value::Jack => 'J',
+            Value::Queen => 'Q',
+            Value::King => 'K',
+            Value::Ace => 'A',
+        }
+    }
+
     fn ranking(&self) -> u8 {
         match self {
             Value::Two => 2,
@@ -153,43 +231,220 @@ impl Value {
 
 impl PartialOrd for Value {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.ranking().cmp(&other.ranking()))
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Value {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(&other).unwrap()
+        self.ranking().cmp(&other.ranking())
+    }
+}
+
+impl Color {
+    /// Every color.
+    pub const ALL: [Color; 4] = [Color::Spade, Color::Diamond, Color::Heart, Color::Club];
+
+    fn symbol(&self) -> char {
+        match self {
+            Color::Spade => 'S',
+            Color::Diamond => 'D',
+            Color::Heart => 'H',
+            Color::Club => 'C',
+        }
     }
 }
 
 impl PartialOrd for PokerCard {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.value.cmp(&other.value))
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PokerCard {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value
+            .cmp(&other.value)
+            .then_with(|| self.color.cmp(&other.color))
+    }
+}
+
+impl PokerCard {
+    pub fn new(value: Value, color: Color) -> Self {
+        PokerCard { value, color }
+    }
+
+    pub fn value(&self) -> Value {
+        self.value
+    }
+
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    /// The 52 cards of a standard deck.
+    fn deck() -> Vec<PokerCard> {
+        Color::ALL
+            .iter()
+            .flat_map(|&color| {
+                Value::ALL
+                    .iter()
+                    .map(move |&value| PokerCard { value, color })
+            })
+            .collect()
+    }
+}
+
+/// Where a card which could not be parsed was found: its index among the cards which were parsed,
+/// the byte offset at which it starts and its text.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CardPosition {
+    pub index: usize,
+    pub offset: usize,
+    pub text: String,
+}
+
+impl CardPosition {
+    // the position of a card parsed on its own
+    fn of(text: &str) -> Self {
+        CardPosition {
+            index: 0,
+            offset: 0,
+            text: text.to_string(),
+        }
     }
 }
 
+/// The reasons why a card or a hand could not be parsed.
 #[derive(PartialEq, Debug)]
-enum PokerHandFromStrConversionError {
-    CardsAmountError,
-    CardLenError,
-    UnknownColor,
-    UnknownValue,
+pub enum PokerHandFromStrConversionError {
+    /// The amount of cards which were found.
+    CardsAmountError(usize),
+    CardLenError(CardPosition),
+    UnknownColor(CardPosition),
+    UnknownValue(CardPosition),
+    DuplicateCard(CardPosition),
     ColorLenError,
     ValueLenError,
 }
 
-#[derive(PartialEq)]
-enum HandCombo {
-    RoyalFlush, // no need to store anything cause any royal flush is equal to another
-    StraightFlush(Value), // storing the highest value
-    FourOfAKind(Value), // storing the value
-    FullHouse([Value; 2]), // storing the 2 values
-    Flush([Value; 5]), // storing the 5 values
-    Straight(Value), // storing the highest value
+impl PokerHandFromStrConversionError {
+    /// The card which could not be parsed, if the error comes from a single card.
+    pub fn position(&self) -> Option<&CardPosition> {
+        match self {
+            PokerHandFromStrConversionError::CardLenError(position)
+            | PokerHandFromStrConversionError::UnknownColor(position)
+            | PokerHandFromStrConversionError::UnknownValue(position)
+            | PokerHandFromStrConversionError::DuplicateCard(position) => Some(position),
+            _ => None,
+        }
+    }
+
+    // places the card which could not be parsed among the other cards
+    pub(crate) fn at(mut self, index: usize, offset: usize) -> Self {
+        if let PokerHandFromStrConversionError::CardLenError(position)
+        | PokerHandFromStrConversionError::UnknownColor(position)
+        | PokerHandFromStrConversionError::UnknownValue(position)
+        | PokerHandFromStrConversionError::DuplicateCard(position) = &mut self
+        {
+            position.index = index;
+            position.offset = offset;
+        }
+        self
+    }
+}
+
+impl fmt::Display for PokerHandFromStrConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            PokerHandFromStrConversionError::CardsAmountError(amount) => {
+                return write!(f, "a hand must have 5 cards, found {}", amount)
+            }
+            PokerHandFromStrConversionError::CardLenError(_) => {
+                "a card must be a value followed by a color"
+            }
+            PokerHandFromStrConversionError::UnknownColor(_) => "unknown card color",
+            PokerHandFromStrConversionError::UnknownValue(_) => "unknown card value",
+            PokerHandFromStrConversionError::DuplicateCard(_) => "the card appears twice",
+            PokerHandFromStrConversionError::ColorLenError => "a color must have 1 character",
+            PokerHandFromStrConversionError::ValueLenError => {
+                "a value must have 1 character, or be 10"
+            }
+        };
+
+        match self.position() {
+            Some(position) => write!(
+                f,
+                "{} in \"{}\" (card {} at byte {})",
+                message, position.text, position.index, position.offset
+            ),
+            None => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for PokerHandFromStrConversionError {}
+
+/// A hand which could not be compared with the others: its index, and why it is invalid.
+#[derive(PartialEq, Debug)]
+pub struct InvalidHandError {
+    pub hand: usize,
+    pub error: PokerHandFromStrConversionError,
+}
+
+impl fmt::Display for InvalidHandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hand {}: {}", self.hand, self.error)
+    }
+}
+
+impl Error for InvalidHandError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl fmt::Display for PokerCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.value, self.color)
+    }
+}
+
+impl fmt::Display for PokerHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cards: Vec<String> = self.0.iter().map(PokerCard::to_string).collect();
+        write!(f, "{}", cards.join(" "))
+    }
+}
+
+/// The combos a hand can be made of, from the best to the worst one.
+///
+/// Combos of the same kind are compared by their values.
+/// A five of a kind can only be made with wild cards.
+#[derive(PartialEq, Debug, Clone)]
+pub enum HandCombo {
+    FiveOfAKind(Value),    // storing the value
+    RoyalFlush,            // no need to store anything cause any royal flush is equal to another
+    StraightFlush(Value), // storing the highest value, which is the five for an ace-to-five straight
+    FourOfAKind(Value),   // storing the value
+    FullHouse([Value; 2]), // storing the value of the three of a kind, then the value of the pair
+    Flush([Value; 5]),    // storing the 5 values
+    Straight(Value), // storing the highest value, which is the five for an ace-to-five straight
     ThreeOfAKind(Value), // storing the value
     TwoPairs([Value; 2]), // storing the 2 values
-    Pair(Value), // storing the value
+    Pair(Value),     // storing the value
     HighCard(Value), // storing the value
 }
 
@@ -206,121 +461,86 @@ impl HandCombo {
             HandCombo::FourOfAKind(_) => 8,
             HandCombo::StraightFlush(_) => 9,
             HandCombo::RoyalFlush => 10,
+            HandCombo::FiveOfAKind(_) => 11,
         }
     }
+
+    // the order in which the values are stored does not matter for two pairs and flushes,
+    // so they are compared from the highest to the lowest
+    fn sorted_values(values: &[Value]) -> Vec<Value> {
+        let mut values = values.to_vec();
+        values.sort_unstable_by(|a, b| b.cmp(a));
+        values
+    }
 }
 
 impl PartialOrd for HandCombo {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match self.ranking().cmp(&other.ranking()) {
-            Ordering::Greater => Some(Ordering::Greater),
-            Ordering::Less => Some(Ordering::Less),
-            Ordering::Equal => match self {
-                HandCombo::RoyalFlush => Some(Ordering::Equal),
-                HandCombo::StraightFlush(self_highest) => {
-                    if let HandCombo::StraightFlush(other_highest) = other {
-                        return Some(self_highest.cmp(other_highest));
-                    }
-                    unreachable!()
-                }
-                HandCombo::FourOfAKind(self_highest) => {
-                    if let HandCombo::FourOfAKind(other_highest) = other {
-                        return Some(self_highest.cmp(other_highest));
-                    }
-                    unreachable!()
-                }
-                HandCombo::FullHouse(self_values) => {
-                    if let HandCombo::FullHouse(other_values) = other {
-                        let mut self_values =
-                            self_values.iter().map(|n| *n).collect::<Vec<Value>>();
-                        let mut other_values =
-                            other_values.iter().map(|n| *n).collect::<Vec<Value>>();
-
-                        self_values.sort_unstable();
-                        other_values.sort_unstable();
-
-                        let self_values = self_values.iter().rev().collect::<Vec<&Value>>();
-                        let other_values = other_values.iter().rev().collect::<Vec<&Value>>();
-
-                        return Some(self_values.cmp(&other_values));
-                    }
-                    unreachable!()
-                }
-                HandCombo::Flush(self_values) => {
-                    if let HandCombo::Flush(other_values) = other {
-                        let mut self_values =
-                            self_values.iter().map(|n| *n).collect::<Vec<Value>>();
-                        let mut other_values =
-                            other_values.iter().map(|n| *n).collect::<Vec<Value>>();
-
-                        self_values.sort_unstable();
-                        other_values.sort_unstable();
-
-                        let self_values = self_values.iter().rev().collect::<Vec<&Value>>();
-                        let other_values = other_values.iter().rev().collect::<Vec<&Value>>();
-
-                        return Some(self_values.cmp(&other_values));
-                    }
-                    unreachable!()
-                }
-                HandCombo::Straight(self_highest) => {
-                    if let HandCombo::Straight(other_highest) = other {
-                        return Some(self_highest.cmp(other_highest));
-                    }
-                    unreachable!()
-                }
-                HandCombo::ThreeOfAKind(self_highest) => {
-                    if let HandCombo::ThreeOfAKind(other_highest) = other {
-                        return Some(self_highest.cmp(other_highest));
-                    }
-                    unreachable!()
+            Ordering::Equal => match (self, other) {
+                (HandCombo::RoyalFlush, HandCombo::RoyalFlush) => Some(Ordering::Equal),
+                (HandCombo::FiveOfAKind(self_highest), HandCombo::FiveOfAKind(other_highest))
+                | (
+                    HandCombo::StraightFlush(self_highest),
+                    HandCombo::StraightFlush(other_highest),
+                )
+                | (HandCombo::FourOfAKind(self_highest), HandCombo::FourOfAKind(other_highest))
+                | (HandCombo::Straight(self_highest), HandCombo::Straight(other_highest))
+                | (HandCombo::ThreeOfAKind(self_highest), HandCombo::ThreeOfAKind(other_highest))
+                | (HandCombo::Pair(self_highest), HandCombo::Pair(other_highest))
+                | (HandCombo::HighCard(self_highest), HandCombo::HighCard(other_highest)) => {
+                    Some(self_highest.cmp(other_highest))
                 }
-                HandCombo::TwoPairs(self_values) => {
-                    if let HandCombo::TwoPairs(other_values) = other {
-                        let mut self_values =
-                            self_values.iter().map(|n| *n).collect::<Vec<Value>>();
-                        let mut other_values =
-                            other_values.iter().map(|n| *n).collect::<Vec<Value>>();
-
-                        self_values.sort_unstable();
-                        other_values.sort_unstable();
-
-                        let self_values = self_values.iter().rev().collect::<Vec<&Value>>();
-                        let other_values = other_values.iter().rev().collect::<Vec<&Value>>();
-
-                        return Some(self_values.cmp(&other_values));
-                    }
-                    unreachable!()
+                // a full house is decided by its three of a kind first, so the order matters here
+                (HandCombo::FullHouse(self_values), HandCombo::FullHouse(other_values)) => {
+                    Some(self_values.cmp(other_values))
                 }
-                HandCombo::Pair(self_highest) => {
-                    if let HandCombo::Pair(other_highest) = other {
-                        return Some(self_highest.cmp(other_highest));
-                    }
-                    unreachable!()
+                (HandCombo::Flush(self_values), HandCombo::Flush(other_values)) => {
+                    Some(Self::sorted_values(self_values).cmp(&Self::sorted_values(other_values)))
                 }
-                HandCombo::HighCard(self_highest) => {
-                    if let HandCombo::HighCard(other_highest) = other {
-                        return Some(self_highest.cmp(other_highest));
-                    }
-                    unreachable!()
+                (HandCombo::TwoPairs(self_values), HandCombo::TwoPairs(other_values)) => {
+                    Some(Self::sorted_values(self_values).cmp(&Self::sorted_values(other_values)))
                 }
+                _ => unreachable!(), // same ranking means same variant
             },
+            ordering => Some(ordering),
         }
     }
 }
 
-impl Into<Vec<PokerCard>> for PokerHand {
-    fn into(self) -> Vec<PokerCard> {
-        self.0.to_vec()
+impl From<PokerHand> for Vec<PokerCard> {
+    fn from(hand: PokerHand) -> Self {
+        hand.0.to_vec()
     }
 }
 
 impl PokerHand {
+    pub fn new(cards: [PokerCard; 5]) -> Self {
+        PokerHand(cards)
+    }
+
+    pub fn cards(&self) -> &[PokerCard; 5] {
+        &self.0
+    }
+
+    /// The combos the hand is made of, from the best one to the worst one: the first one is what
+    /// the hand is worth, the following ones are its kickers.
+    ///
+    /// Two hands can be compared by comparing their combos.
+    pub fn combos(&self) -> Vec<HandCombo> {
+        self.clone().find_combos()
+    }
+
+    /// Splits the hand into its combos, from the best one to the worst one.
+    ///
+    /// The cards that are not part of the best combo are the kickers, and they end up as
+    /// the following combos: comparing two of those vectors lexicographically is then
+    /// enough to compare two hands.
     fn find_combos(self) -> Vec<HandCombo> {
         let mut hand: Vec<PokerCard> = self.into();
         let mut combos: Vec<HandCombo> = vec![];
 
-        while hand.len() != 0 {
+        while !hand.is_empty() {
             combos.push(Self::find_best_combo(&mut hand));
         }
 
@@ -328,133 +548,171 @@ impl PokerHand {
     }
 
     fn find_best_combo(hand: &mut Vec<PokerCard>) -> HandCombo {
-        hand.sort_by(|a, b| b.partial_cmp(a).unwrap());
-
-        Self::find_royal_flush(hand).unwrap_or(Self::find_straight_flush(hand).unwrap_or(
-            Self::find_four_of_a_kind(hand).unwrap_or(Self::find_full_house(hand).unwrap_or(
-                Self::find_flush(hand).unwrap_or(
-                    Self::find_straight(hand).unwrap_or(
-                        Self::find_three_of_a_kind(hand).unwrap_or(
-                            Self::find_two_pairs(hand).unwrap_or(
-                                Self::find_pair(hand).unwrap_or(Self::find_high_card(hand)),
-                            ),
-                        ),
-                    ),
-                ),
-            )),
-        ))
+        hand.sort_by(|a, b| b.cmp(a));
+
+        // every finder removes the cards it used from the hand, so they must only be called
+        // until one of them succeeds
+        Self::find_five_of_a_kind(hand)
+            .or_else(|| Self::find_royal_flush(hand))
+            .or_else(|| Self::find_straight_flush(hand))
+            .or_else(|| Self::find_four_of_a_kind(hand))
+            .or_else(|| Self::find_full_house(hand))
+            .or_else(|| Self::find_flush(hand))
+            .or_else(|| Self::find_straight(hand))
+            .or_else(|| Self::find_three_of_a_kind(hand))
+            .or_else(|| Self::find_two_pairs(hand))
+            .or_else(|| Self::find_pair(hand))
+            .unwrap_or_else(|| Self::find_high_card(hand))
     }
 
-    fn find_royal_flush(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
+    // the highest value of the straight made by the hand, if it makes one
+    // the hand is expected to be sorted from the highest card to the lowest one
+    fn straight_highest_value(hand: &[PokerCard]) -> Option<Value> {
         if hand.len() != 5 {
             return None;
         }
 
-        if hand.get(0).unwrap().value == Value::Ace
-            && hand.get(1).unwrap().value == Value::King
-            && hand.get(2).unwrap().value == Value::Queen
-            && hand.get(3).unwrap().value == Value::Jack
-            && hand.get(4).unwrap().value == Value::Ten
+        let values: Vec<Value> = hand.iter().map(|card| card.value).collect();
+
+        if values
+            .windows(2)
+            .all(|values| values[0].ranking() == values[1].ranking() + 1)
         {
-            for i in (0..hand.len()).rev() {
-                hand.remove(i);
-            }
+            return Some(values[0]);
+        }
 
-            return Some(HandCombo::RoyalFlush);
+        // the ace can also be the lowest card of a straight
+        if values
+            == [
+                Value::Ace,
+                Value::Five,
+                Value::Four,
+                Value::Three,
+                Value::Two,
+            ]
+        {
+            return Some(Value::Five);
         }
 
         None
     }
 
-    fn find_straight_flush(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
-        if hand.len() != 5 {
-            return None;
-        }
+    fn is_flush(hand: &[PokerCard]) -> bool {
+        hand.len() == 5 && hand.iter().all(|card| card.color == hand[0].color)
+    }
 
-        if hand.get(0).unwrap().value.ranking() == hand.get(1).unwrap().value.ranking() + 1
-            && hand.get(1).unwrap().value.ranking() == hand.get(2).unwrap().value.ranking() + 1
-            && hand.get(2).unwrap().value.ranking() == hand.get(3).unwrap().value.ranking() + 1
-            && hand.get(3).unwrap().value.ranking() == hand.get(4).unwrap().value.ranking() + 1
-            && [
-                hand.get(0).unwrap().color,
-                hand.get(1).unwrap().color,
-                hand.get(2).unwrap().color,
-                hand.get(3).unwrap().color,
-                hand.get(4).unwrap().color,
-            ]
-            .iter()
-            .filter(|color| color != &&hand.get(0).unwrap().color)
-            .count()
-                == 0
-        {
-            for i in (1..hand.len()).rev() {
-                hand.remove(i);
+    // the values appearing exactly `amount` times in the hand, keeping the order of the hand
+    fn values_appearing(hand: &[PokerCard], amount: usize) -> Vec<Value> {
+        let mut values: Vec<Value> = vec![];
+
+        for card in hand {
+            if !values.contains(&card.value)
+                && hand
+                    .iter()
+                    .filter(|other| other.value == card.value)
+                    .count()
+                    == amount
+            {
+                values.push(card.value);
             }
-
-            let highest_card = hand.remove(0);
-            return Some(HandCombo::StraightFlush(highest_card.value));
         }
 
-        None
+        values
     }
 
-    fn find_four_of_a_kind(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
-        if hand.len() < 4 {
+    fn remove_value(hand: &mut Vec<PokerCard>, value: Value) {
+        hand.retain(|card| card.value != value);
+    }
+
+    fn find_five_of_a_kind(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
+        let value = *Self::values_appearing(hand, 5).first()?;
+
+        Self::remove_value(hand, value);
+        Some(HandCombo::FiveOfAKind(value))
+    }
+
+    fn find_royal_flush(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
+        if !Self::is_flush(hand) || Self::straight_highest_value(hand) != Some(Value::Ace) {
             return None;
         }
 
-        for i in 0..2 {
-            let value = hand.get(i).unwrap().value;
-            if hand
-                .iter()
-                .filter(|card| card.value == value)
-                .count()
-                == 4
-            {
-                let mut indexes = hand.iter()
-                    .enumerate()
-                    .filter(|(_, card)| card.value == value)
-                    .map(|(i, _)| i)
-                    .collect::<Vec<usize>>();
-
-                indexes.sort();
+        hand.clear();
+        Some(HandCombo::RoyalFlush)
+    }
 
-                for index in indexes.iter().rev()
-                {
-                    hand.remove(*index);
-                }
-            }
+    fn find_straight_flush(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
+        if !Self::is_flush(hand) {
+            return None;
         }
 
-        todo!("find the four of a kind if there is one")
+        let highest_value = Self::straight_highest_value(hand)?;
+        hand.clear();
+        Some(HandCombo::StraightFlush(highest_value))
+    }
+
+    fn find_four_of_a_kind(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
+        let value = *Self::values_appearing(hand, 4).first()?;
+
+        Self::remove_value(hand, value);
+        Some(HandCombo::FourOfAKind(value))
     }
 
     fn find_full_house(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
-        unimplemented!()
+        let three_value = *Self::values_appearing(hand, 3).first()?;
+        let pair_value = *Self::values_appearing(hand, 2).first()?;
+
+        Self::remove_value(hand, three_value);
+        Self::remove_value(hand, pair_value);
+        Some(HandCombo::FullHouse([three_value, pair_value]))
     }
 
     fn find_flush(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
-        unimplemented!()
+        if !Self::is_flush(hand) {
+            return None;
+        }
+
+        let values = <[Value; 5]>::try_from(
+            hand.drain(..)
+                .map(|card| card.value)
+                .collect::<Vec<Value>>(),
+        )
+        .unwrap(); // the unwrap is safe because a flush is always made of 5 cards
+        Some(HandCombo::Flush(values))
     }
 
     fn find_straight(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
-        unimplemented!()
+        let highest_value = Self::straight_highest_value(hand)?;
+        hand.clear();
+        Some(HandCombo::Straight(highest_value))
     }
 
     fn find_three_of_a_kind(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
-        unimplemented!()
+        let value = *Self::values_appearing(hand, 3).first()?;
+
+        Self::remove_value(hand, value);
+        Some(HandCombo::ThreeOfAKind(value))
     }
 
     fn find_two_pairs(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
-        unimplemented!()
+        let pairs = Self::values_appearing(hand, 2);
+        if pairs.len() < 2 {
+            return None;
+        }
+
+        Self::remove_value(hand, pairs[0]);
+        Self::remove_value(hand, pairs[1]);
+        Some(HandCombo::TwoPairs([pairs[0], pairs[1]]))
     }
 
     fn find_pair(hand: &mut Vec<PokerCard>) -> Option<HandCombo> {
-        unimplemented!()
+        let value = *Self::values_appearing(hand, 2).first()?;
+
+        Self::remove_value(hand, value);
+        Some(HandCombo::Pair(value))
     }
 
     fn find_high_card(hand: &mut Vec<PokerCard>) -> HandCombo {
-        unimplemented!()
+        HandCombo::HighCard(hand.remove(0).value)
     }
 }
 
@@ -462,30 +720,28 @@ impl PokerHand {
 fn basic_success() {
     assert_eq!(
         "2S 4S 7H AC JH".parse::<PokerHand>(),
-        Ok(PokerHand {
-            0: [
-                PokerCard {
-                    color: Color::Spade,
-                    value: Value::Two
-                },
-                PokerCard {
-                    color: Color::Spade,
-                    value: Value::Four
-                },
-                PokerCard {
-                    color: Color::Heart,
-                    value: Value::Seven
-                },
-                PokerCard {
-                    color: Color::Club,
-                    value: Value::Ace
-                },
-                PokerCard {
-                    color: Color::Heart,
-                    value: Value::Jack
-                },
-            ]
-        })
+        Ok(PokerHand([
+            PokerCard {
+                color: Color::Spade,
+                value: Value::Two
+            },
+            PokerCard {
+                color: Color::Spade,
+                value: Value::Four
+            },
+            PokerCard {
+                color: Color::Heart,
+                value: Value::Seven
+            },
+            PokerCard {
+                color: Color::Club,
+                value: Value::Ace
+            },
+            PokerCard {
+                color: Color::Heart,
+                value: Value::Jack
+            },
+        ]))
     )
 }
 
@@ -493,7 +749,7 @@ fn basic_success() {
 fn empty() {
     assert_eq!(
         "".parse::<PokerHand>(),
-        Err(PokerHandFromStrConversionError::CardsAmountError)
+        Err(PokerHandFromStrConversionError::CardsAmountError(0))
     )
 }
 
@@ -501,7 +757,7 @@ fn empty() {
 fn not_enough_cards() {
     assert_eq!(
         "2S 4S 7H AC".parse::<PokerHand>(),
-        Err(PokerHandFromStrConversionError::CardsAmountError)
+        Err(PokerHandFromStrConversionError::CardsAmountError(4))
     )
 }
 
@@ -509,7 +765,13 @@ fn not_enough_cards() {
 fn too_big_card() {
     assert_eq!(
         "2S 4S 7H AC JHH".parse::<PokerHand>(),
-        Err(PokerHandFromStrConversionError::CardLenError)
+        Err(PokerHandFromStrConversionError::CardLenError(
+            CardPosition {
+                index: 4,
+                offset: 12,
+                text: "JHH".to_string()
+            }
+        ))
     )
 }
 
@@ -517,7 +779,13 @@ fn too_big_card() {
 fn too_small_card() {
     assert_eq!(
         "2S 4S 7H AC J".parse::<PokerHand>(),
-        Err(PokerHandFromStrConversionError::CardLenError)
+        Err(PokerHandFromStrConversionError::CardLenError(
+            CardPosition {
+                index: 4,
+                offset: 12,
+                text: "J".to_string()
+            }
+        ))
     )
 }
 
@@ -525,7 +793,13 @@ fn too_small_card() {
 fn wrong_color() {
     assert_eq!(
         "2S 4S 7H AC JX".parse::<PokerHand>(),
-        Err(PokerHandFromStrConversionError::UnknownColor)
+        Err(PokerHandFromStrConversionError::UnknownColor(
+            CardPosition {
+                index: 4,
+                offset: 12,
+                text: "JX".to_string()
+            }
+        ))
     )
 }
 
@@ -533,7 +807,13 @@ fn wrong_color() {
 fn wrong_value() {
     assert_eq!(
         "2S 4S 7H AC ZH".parse::<PokerHand>(),
-        Err(PokerHandFromStrConversionError::UnknownValue)
+        Err(PokerHandFromStrConversionError::UnknownValue(
+            CardPosition {
+                index: 4,
+                offset: 12,
+                text: "ZH".to_string()
+            }
+        ))
     )
 }
 
@@ -573,10 +853,19 @@ fn same_combos_two_elements() {
 
 #[test]
 fn same_combos_two_elements_equal() {
+    assert_eq!(
+        HandCombo::TwoPairs([Value::Three, Value::Nine])
+            .partial_cmp(&HandCombo::TwoPairs([Value::Nine, Value::Three])),
+        Some(Ordering::Equal)
+    )
+}
+
+#[test]
+fn full_house_three_of_a_kind_first() {
     assert_eq!(
         HandCombo::FullHouse([Value::Three, Value::Nine])
             .partial_cmp(&HandCombo::FullHouse([Value::Nine, Value::Three])),
-        Some(Ordering::Equal)
+        Some(Ordering::Less)
     )
 }
 
@@ -601,6 +890,133 @@ fn same_combos_five_elements() {
     )
 }
 
+#[test]
+fn pair_combos_with_kickers() {
+    assert_eq!(
+        "9C KS 3D 7H KD".parse::<PokerHand>().unwrap().find_combos(),
+        vec![
+            HandCombo::Pair(Value::King),
+            HandCombo::HighCard(Value::Nine),
+            HandCombo::HighCard(Value::Seven),
+            HandCombo::HighCard(Value::Three),
+        ]
+    )
+}
+
+#[test]
+fn five_of_a_kind_beats_royal_flush() {
+    assert_eq!(
+        HandCombo::FiveOfAKind(Value::Two).partial_cmp(&HandCombo::RoyalFlush),
+        Some(Ordering::Greater)
+    )
+}
+
+#[test]
+fn ace_to_five_straight_combos() {
+    assert_eq!(
+        "3C AS 5D 2H 4C".parse::<PokerHand>().unwrap().find_combos(),
+        vec![HandCombo::Straight(Value::Five)]
+    )
+}
+
+#[test]
+fn ace_to_five_straight_flush_is_not_royal() {
+    assert_eq!(
+        "3C AC 5C 2C 4C".parse::<PokerHand>().unwrap().find_combos(),
+        vec![HandCombo::StraightFlush(Value::Five)]
+    )
+}
+
+#[test]
+fn full_house_combos() {
+    assert_eq!(
+        "4S 9D 4H 9C 4C".parse::<PokerHand>().unwrap().find_combos(),
+        vec![HandCombo::FullHouse([Value::Four, Value::Nine])]
+    )
+}
+
+#[test]
+fn royal_flush_needs_a_flush() {
+    assert_eq!(
+        "AS KS QS JS TD".parse::<PokerHand>().unwrap().find_combos(),
+        vec![HandCombo::Straight(Value::Ace)]
+    )
+}
+
+/// Given a list of poker hands, return a list of those hands which win.
+///
+/// Note the type signature: this function should return _the same_ reference to
+/// the winning hand(s) as were passed in, not reconstructed strings which happen to be equal.
+///
+/// Hands which cannot be parsed are not taken into account.
 pub fn winning_hands<'a>(hands: &[&'a str]) -> Vec<&'a str> {
-    unimplemented!("Out of {:?}, which hand wins?", hands)
+    winners_by_combos(
+        hands
+            .iter()
+            .filter_map(|hand| Some((*hand, hand.parse::<PokerHand>().ok()?.find_combos()))),
+    )
+}
+
+/// Like `winning_hands`, but fails on the first hand which cannot be parsed,
+/// or which holds a card already found in an earlier hand.
+pub fn try_winning_hands<'a>(hands: &[&'a str]) -> Result<Vec<&'a str>, InvalidHandError> {
+    let mut seen_cards: Vec<PokerCard> = vec![];
+    let mut combos: Vec<(&'a str, Vec<HandCombo>)> = vec![];
+
+    for (hand_index, hand) in hands.iter().enumerate() {
+        let invalid_hand = |error| InvalidHandError {
+            hand: hand_index,
+            error,
+        };
+        let parsed: PokerHand = hand.parse().map_err(invalid_hand)?;
+
+        for (index, (offset, text)) in card_texts(hand).enumerate() {
+            if seen_cards.contains(&parsed.0[index]) {
+                return Err(invalid_hand(
+                    PokerHandFromStrConversionError::DuplicateCard(CardPosition {
+                        index,
+                        offset,
+                        text: text.to_string(),
+                    }),
+                ));
+            }
+        }
+
+        seen_cards.extend(parsed.0.iter().cloned());
+        combos.push((*hand, parsed.find_combos()));
+    }
+
+    Ok(winners_by_combos(combos.into_iter()))
+}
+
+// keeps every hand whose combos are the best ones, in the order they were given
+fn winners_by_combos<H>(hands: impl Iterator<Item = (H, Vec<HandCombo>)>) -> Vec<H> {
+    winners_by(hands, |a, b| a.partial_cmp(b))
+}
+
+// keeps every hand which compares as the best one, in the order they were given
+fn winners_by<H, T>(
+    hands: impl Iterator<Item = (H, T)>,
+    compare: impl Fn(&T, &T) -> Option<Ordering>,
+) -> Vec<H> {
+    let mut winners: Vec<H> = vec![];
+    let mut best: Option<T> = None;
+
+    for (hand, value) in hands {
+        let ordering = match &best {
+            Some(best) => compare(&value, best),
+            None => Some(Ordering::Greater),
+        };
+
+        match ordering {
+            Some(Ordering::Greater) => {
+                winners = vec![hand];
+                best = Some(value);
+            }
+            Some(Ordering::Equal) => winners.push(hand),
+            _ => {}
+        }
+    }
+
+    winners
 }
@@ -0,0 +1,116 @@
+/// Chips which can be won by a set of players, identified by their seat index.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Pot {
+    pub amount: u32,
+    pub eligible: Vec<usize>,
+}
+
+impl Pot {
+    /// Splits the pot between its winners, the odd chips going to the first winners.
+    pub fn split(&self, winners: &[usize]) -> Vec<(usize, u32)> {
+        if winners.is_empty() {
+            return vec![];
+        }
+
+        let share = self.amount / winners.len() as u32;
+        let odd_chips = self.amount as usize % winners.len();
+
+        winners
+            .iter()
+            .enumerate()
+            .map(|(i, &winner)| (winner, share + if i < odd_chips { 1 } else { 0 }))
+            .collect()
+    }
+}
+
+/// Builds the main pot and the side pots out of the chips every player put in,
+/// the main pot coming first.
+///
+/// Players who folded cannot win any pot, but their chips still go in the pots.
+pub fn side_pots(contributions: &[u32], folded: &[bool]) -> Vec<Pot> {
+    // every player who is all in for less than the others creates a new pot
+    let mut levels: Vec<u32> = contributions
+        .iter()
+        .zip(folded)
+        .filter(|(&amount, &folded)| amount > 0 && !folded)
+        .map(|(&amount, _)| amount)
+        .collect();
+    levels.sort_unstable();
+    levels.dedup();
+
+    let mut pots: Vec<Pot> = vec![];
+    let mut previous_level = 0;
+
+    for level in levels {
+        let amount = contributions
+            .iter()
+            .map(|&contribution| contribution.min(level) - contribution.min(previous_level))
+            .sum();
+        let eligible = (0..contributions.len())
+            .filter(|&i| !folded[i] && contributions[i] >= level)
+            .collect();
+
+        pots.push(Pot { amount, eligible });
+        previous_level = level;
+    }
+
+    // the chips of the players who folded after putting in more than anyone who stayed
+    let dead_chips: u32 = contributions
+        .iter()
+        .map(|&contribution| contribution.saturating_sub(previous_level))
+        .sum();
+    if let Some(last_pot) = pots.last_mut() {
+        last_pot.amount += dead_chips;
+    }
+
+    pots
+}
+
+#[test]
+fn single_pot_without_all_in() {
+    assert_eq!(
+        side_pots(&[100, 100, 50], &[false, false, true]),
+        vec![Pot {
+            amount: 250,
+            eligible: vec![0, 1]
+        }]
+    );
+}
+
+#[test]
+fn side_pot_for_short_all_in() {
+    assert_eq!(
+        side_pots(&[500, 1000, 1000], &[false, false, false]),
+        vec![
+            Pot {
+                amount: 1500,
+                eligible: vec![0, 1, 2]
+            },
+            Pot {
+                amount: 1000,
+                eligible: vec![1, 2]
+            }
+        ]
+    );
+}
+
+#[test]
+fn folded_chips_above_every_level() {
+    assert_eq!(
+        side_pots(&[300, 100, 100], &[true, false, false]),
+        vec![Pot {
+            amount: 500,
+            eligible: vec![1, 2]
+        }]
+    );
+}
+
+#[test]
+fn odd_chips_go_to_the_first_winners() {
+    let pot = Pot {
+        amount: 101,
+        eligible: vec![0, 1, 2],
+    };
+
+    assert_eq!(pot.split(&[2, 0]), vec![(2, 51), (0, 50)]);
+}
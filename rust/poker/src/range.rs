@@ -0,0 +1,353 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::equity::cards_equity;
+use crate::holdem::parse_cards;
+use crate::{Color, Equity, EquityMethod, PokerCard, Value};
+
+/// Two hole cards a player may hold, with the weight of this combo in the range,
+/// from `0.0` (never held) to `1.0` (always held).
+#[derive(Clone, PartialEq, Debug)]
+pub struct WeightedCombo {
+    pub cards: [PokerCard; 2],
+    pub weight: f64,
+}
+
+/// The hole cards a player may hold, written in the usual range notation:
+/// hands separated by commas, like `"AKs, TT+, 76s-54s, AQo+"`.
+///
+/// * `"TT"` is a pair, `"AKs"` two suited cards, `"AKo"` two offsuit cards and `"AK"` both.
+/// * `"TT+"` is every pair from tens to aces, and `"AQo+"` every offsuit ace with a queen or better.
+/// * `"TT-77"` is every pair from sevens to tens, `"KTs-K7s"` every suited king with a seven to
+///   a ten, and `"76s-54s"` the suited connectors from 5-4 to 7-6.
+/// * `"AhKh"` is a single combo.
+/// * A hand can be followed by a weight, like `"AKs:0.5"`.
+///
+/// A combo given twice keeps its first weight.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Range {
+    combos: Vec<WeightedCombo>,
+}
+
+/// The reasons why a range could not be parsed, with the text of the hand at fault.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RangeError {
+    UnknownHand(String),
+    InvalidWeight(String),
+    InvalidSpan(String),
+}
+
+// the suits the two cards of a hand class can have
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Suits {
+    Suited,
+    Offsuit,
+    Any,
+}
+
+// a hand of the range notation like "AKs" or "TT", the first value being the highest one
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+struct HandClass {
+    high: Value,
+    low: Value,
+    suits: Suits,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::UnknownHand(hand) => write!(f, "unknown hand \"{}\"", hand),
+            RangeError::InvalidWeight(hand) => {
+                write!(f, "the weight of \"{}\" must be between 0 and 1", hand)
+            }
+            RangeError::InvalidSpan(hand) => {
+                write!(f, "\"{}\" is not a span of similar hands", hand)
+            }
+        }
+    }
+}
+
+impl Error for RangeError {}
+
+impl FromStr for HandClass {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+
+        let (high, low, suits) = match chars.as_slice() {
+            [high, low] => (*high, *low, Suits::Any),
+            [high, low, 's'] => (*high, *low, Suits::Suited),
+            [high, low, 'o'] => (*high, *low, Suits::Offsuit),
+            _ => return Err(()),
+        };
+        let high: Value = high.to_string().parse().map_err(|_| ())?;
+        let low: Value = low.to_string().parse().map_err(|_| ())?;
+
+        match (high == low, suits) {
+            (true, Suits::Any) => Ok(HandClass { high, low, suits }),
+            (true, _) => Err(()), // a pair can be neither suited nor offsuit
+            (false, _) => Ok(HandClass {
+                high: high.max(low),
+                low: high.min(low),
+                suits,
+            }),
+        }
+    }
+}
+
+impl HandClass {
+    fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    fn combos(&self) -> Vec<[PokerCard; 2]> {
+        let mut combos = vec![];
+
+        for (i, &high_color) in Color::ALL.iter().enumerate() {
+            for (j, &low_color) in Color::ALL.iter().enumerate() {
+                let keep = match (self.is_pair(), self.suits) {
+                    (true, _) => i < j,
+                    (false, Suits::Suited) => i == j,
+                    (false, Suits::Offsuit) => i != j,
+                    (false, Suits::Any) => true,
+                };
+
+                if keep {
+                    combos.push([
+                        PokerCard::new(self.high, high_color),
+                        PokerCard::new(self.low, low_color),
+                    ]);
+                }
+            }
+        }
+
+        combos
+    }
+
+    // "TT+" goes up to aces, and "AQo+" raises the lowest card up to just below the highest one
+    fn and_better(self) -> Vec<HandClass> {
+        let top = match self.is_pair() {
+            true => Value::Ace,
+            false => Value::ALL[value_index(self.high) - 1],
+        };
+
+        self.span_to(top)
+    }
+
+    // moves the lowest card (and the highest one for pairs) up to the given value
+    fn span_to(self, top: Value) -> Vec<HandClass> {
+        (value_index(self.low)..=value_index(top))
+            .map(|i| HandClass {
+                high: match self.is_pair() {
+                    true => Value::ALL[i],
+                    false => self.high,
+                },
+                low: Value::ALL[i],
+                suits: self.suits,
+            })
+            .collect()
+    }
+
+    // every class between two classes of the same shape, like "76s-54s" or "KTs-K7s"
+    fn span(self, other: HandClass) -> Option<Vec<HandClass>> {
+        let (top, bottom) = match self.low >= other.low {
+            true => (self, other),
+            false => (other, self),
+        };
+
+        if top.suits != bottom.suits || top.is_pair() != bottom.is_pair() {
+            return None;
+        }
+
+        if top.is_pair() || top.high == bottom.high {
+            return Some(bottom.span_to(top.low));
+        }
+
+        // connectors keep the same gap between their cards
+        let gap = value_index(top.high) - value_index(top.low);
+        if value_index(bottom.high) - value_index(bottom.low) != gap {
+            return None;
+        }
+
+        Some(
+            (value_index(bottom.low)..=value_index(top.low))
+                .map(|i| HandClass {
+                    high: Value::ALL[i + gap],
+                    low: Value::ALL[i],
+                    suits: top.suits,
+                })
+                .collect(),
+        )
+    }
+}
+
+fn value_index(value: Value) -> usize {
+    Value::ALL.iter().position(|&v| v == value).unwrap() // the unwrap is safe because every value is in ALL
+}
+
+impl FromStr for Range {
+    type Err = RangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut range = Range::default();
+
+        for item in s.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let (hand, weight) = match item.split_once(':') {
+                Some((hand, weight)) => {
+                    let weight: f64 = weight
+                        .trim()
+                        .parse()
+                        .map_err(|_| RangeError::InvalidWeight(item.to_string()))?;
+                    if !(0.0..=1.0).contains(&weight) {
+                        return Err(RangeError::InvalidWeight(item.to_string()));
+                    }
+                    (hand.trim(), weight)
+                }
+                None => (item, 1.0),
+            };
+
+            for cards in hand_combos(hand)? {
+                range.add(cards, weight);
+            }
+        }
+
+        Ok(range)
+    }
+}
+
+// the combos of a single hand of a range, without its weight
+fn hand_combos(hand: &str) -> Result<Vec<[PokerCard; 2]>, RangeError> {
+    let unknown_hand = || RangeError::UnknownHand(hand.to_string());
+
+    let classes = if let Some(class) = hand.strip_suffix('+') {
+        class
+            .parse::<HandClass>()
+            .map_err(|_| unknown_hand())?
+            .and_better()
+    } else if let Some((first, last)) = hand.split_once('-') {
+        let first: HandClass = first.parse().map_err(|_| unknown_hand())?;
+        let last: HandClass = last.parse().map_err(|_| unknown_hand())?;
+
+        first
+            .span(last)
+            .ok_or_else(|| RangeError::InvalidSpan(hand.to_string()))?
+    } else if let Ok(class) = hand.parse::<HandClass>() {
+        vec![class]
+    } else {
+        // a single combo, like "AhKh"
+        let split = hand.char_indices().nth(2).map_or(0, |(i, _)| i);
+        let (first, second) = hand.split_at(split);
+        let cards = parse_cards(&format!("{} {}", first, second)).map_err(|_| unknown_hand())?;
+
+        return match <[PokerCard; 2]>::try_from(cards) {
+            Ok(cards) => Ok(vec![cards]),
+            Err(_) => Err(unknown_hand()),
+        };
+    };
+
+    Ok(classes.iter().flat_map(HandClass::combos).collect())
+}
+
+impl Range {
+    pub fn combos(&self) -> &[WeightedCombo] {
+        &self.combos
+    }
+
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// The sum of the weights of the combos.
+    pub fn total_weight(&self) -> f64 {
+        self.combos.iter().map(|combo| combo.weight).sum()
+    }
+
+    /// The range without the combos holding one of the given cards, which the player cannot have
+    /// when the cards are known to be somewhere else.
+    pub fn without_cards(&self, known_cards: &[PokerCard]) -> Range {
+        Range {
+            combos: self
+                .combos
+                .iter()
+                .filter(|combo| !combo.cards.iter().any(|card| known_cards.contains(card)))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn add(&mut self, cards: [PokerCard; 2], weight: f64) {
+        let already_added = self
+            .combos
+            .iter()
+            .any(|combo| combo.cards.contains(&cards[0]) && combo.cards.contains(&cards[1]));
+
+        if !already_added {
+            self.combos.push(WeightedCombo { cards, weight });
+        }
+    }
+}
+
+/// Given the hole cards of a player, the range of their opponent and the cards already on
+/// the board (possibly none), compute the equity of the player against the whole range.
+///
+/// The combos of the range holding a known card are left out, and the others count as much as
+/// their weight. Returns `None` if the cards cannot be parsed or are known twice, if there are
+/// more than five cards on the board, or if no combo of the range is left.
+pub fn range_equity(
+    hole_cards: &str,
+    range: &Range,
+    board: &str,
+    method: EquityMethod,
+) -> Option<Equity> {
+    let hole_cards = parse_cards(hole_cards).ok()?;
+    let board = parse_cards(board).ok()?;
+
+    let known_cards: Vec<PokerCard> = hole_cards.iter().chain(board.iter()).cloned().collect();
+    let range = range.without_cards(&known_cards);
+    let total_weight = range.total_weight();
+
+    if total_weight == 0.0 {
+        return None;
+    }
+
+    let mut equity = Equity::default();
+    for combo in range.combos() {
+        let players = [hole_cards.clone(), combo.cards.to_vec()];
+        let combo_equity = cards_equity(&players, &board, method)?[0];
+        let share = combo.weight / total_weight;
+
+        equity.win += combo_equity.win * share;
+        equity.tie += combo_equity.tie * share;
+        equity.loss += combo_equity.loss * share;
+    }
+
+    Some(equity)
+}
+
+#[test]
+fn connectors_keep_their_gap() {
+    let first: HandClass = "J9s".parse().unwrap();
+    let last: HandClass = "75s".parse().unwrap();
+
+    assert_eq!(
+        first.span(last),
+        Some(vec![
+            "75s".parse().unwrap(),
+            "86s".parse().unwrap(),
+            "97s".parse().unwrap(),
+            "T8s".parse().unwrap(),
+            "J9s".parse().unwrap(),
+        ])
+    );
+}
+
+#[test]
+fn pairs_cannot_be_suited() {
+    assert_eq!("TTs".parse::<HandClass>(), Err(()));
+}
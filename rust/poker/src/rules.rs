@@ -0,0 +1,239 @@
+use std::cmp::Ordering;
+
+use crate::holdem::{combinations, parse_cards, parse_cards_after};
+use crate::{winners_by, HandCombo, PokerCard, PokerHand, Value};
+
+/// The ways hands can be ranked.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RuleSet {
+    /// The standard rules: the highest hand wins.
+    High,
+    /// The lowest hand wins, aces are low and straights and flushes do not count,
+    /// so the best hand is 5-4-3-2-A.
+    AceToFiveLowball,
+    /// The lowest hand wins, aces are high and straights and flushes count,
+    /// so the best hand is 7-5-4-3-2 of different colors.
+    DeuceToSevenLowball,
+    /// The highest hand wins, but it must be made of exactly two hole cards and three board cards.
+    Omaha,
+    /// The highest hand wins, but it is played with a deck without the twos to the fives:
+    /// the ace can end the A-6-7-8-9 straight, and a flush beats a full house.
+    ShortDeck,
+}
+
+// a combo as it is compared under a rule set: its ranking, then its values
+type ComboKey = (u8, Vec<u8>);
+
+impl RuleSet {
+    /// The combos of the best five-card hand the player can make with their hole cards and the
+    /// board, from the best one to the worst one.
+    ///
+    /// Returns `None` if no five-card hand can be made, if a card cannot be played with these rules,
+    /// or if a card is both in the hole cards and on the board.
+    pub fn best_combos(
+        self,
+        hole_cards: &[PokerCard],
+        board: &[PokerCard],
+    ) -> Option<Vec<HandCombo>> {
+        if hole_cards.iter().any(|card| board.contains(card)) {
+            return None;
+        }
+        if self == RuleSet::ShortDeck
+            && hole_cards
+                .iter()
+                .chain(board.iter())
+                .any(|card| card.value < Value::Six)
+        {
+            return None;
+        }
+
+        let hands: Vec<Vec<PokerCard>> = match self {
+            RuleSet::Omaha => {
+                let mut hands = vec![];
+                for hole_indexes in combinations(hole_cards.len(), 2) {
+                    for board_indexes in combinations(board.len(), 3) {
+                        hands.push(
+                            hole_indexes
+                                .iter()
+                                .map(|&i| hole_cards[i].clone())
+                                .chain(board_indexes.iter().map(|&i| board[i].clone()))
+                                .collect(),
+                        );
+                    }
+                }
+                hands
+            }
+            _ => {
+                let cards: Vec<PokerCard> =
+                    hole_cards.iter().chain(board.iter()).cloned().collect();
+                combinations(cards.len(), 5)
+                    .map(|indexes| indexes.iter().map(|&i| cards[i].clone()).collect())
+                    .collect()
+            }
+        };
+
+        hands
+            .into_iter()
+            .map(|cards| {
+                // the unwrap is safe because every hand was made of 5 cards
+                self.combos(PokerHand(<[PokerCard; 5]>::try_from(cards).unwrap()))
+            })
+            .max_by(|a, b| self.compare(a, b))
+    }
+
+    /// Compares the combos of two hands, `Ordering::Greater` meaning that the first hand is the best one.
+    pub fn compare(self, a: &[HandCombo], b: &[HandCombo]) -> Ordering {
+        match self {
+            RuleSet::AceToFiveLowball | RuleSet::DeuceToSevenLowball => {
+                self.key(b).cmp(&self.key(a))
+            }
+            _ => self.key(a).cmp(&self.key(b)),
+        }
+    }
+
+    fn combos(self, hand: PokerHand) -> Vec<HandCombo> {
+        let mut values: Vec<Value> = hand.0.iter().map(|card| card.value).collect();
+        values.sort_unstable_by(|a, b| b.cmp(a));
+        let is_flush = hand.0.iter().all(|card| card.color == hand.0[0].color);
+
+        let combos = hand.find_combos();
+
+        match (self, &combos[0]) {
+            (
+                RuleSet::AceToFiveLowball,
+                HandCombo::RoyalFlush
+                | HandCombo::StraightFlush(_)
+                | HandCombo::Flush(_)
+                | HandCombo::Straight(_),
+            ) => values.into_iter().map(HandCombo::HighCard).collect(),
+            // the ace is always high, so it cannot start a straight
+            (RuleSet::DeuceToSevenLowball, HandCombo::StraightFlush(Value::Five)) => {
+                // the unwrap is safe because a straight flush is made of 5 cards
+                vec![HandCombo::Flush(<[Value; 5]>::try_from(values).unwrap())]
+            }
+            (RuleSet::DeuceToSevenLowball, HandCombo::Straight(Value::Five)) => {
+                values.into_iter().map(HandCombo::HighCard).collect()
+            }
+            (RuleSet::ShortDeck, HandCombo::HighCard(_) | HandCombo::Flush(_))
+                if values
+                    == [
+                        Value::Ace,
+                        Value::Nine,
+                        Value::Eight,
+                        Value::Seven,
+                        Value::Six,
+                    ] =>
+            {
+                match is_flush {
+                    true => vec![HandCombo::StraightFlush(Value::Nine)],
+                    false => vec![HandCombo::Straight(Value::Nine)],
+                }
+            }
+            _ => combos,
+        }
+    }
+
+    fn key(self, combos: &[HandCombo]) -> Vec<ComboKey> {
+        let value_ranking = |value: &Value| match (self, value) {
+            (RuleSet::AceToFiveLowball, Value::Ace) => 1,
+            _ => value.ranking(),
+        };
+
+        let mut key: Vec<ComboKey> = combos
+            .iter()
+            .map(|combo| {
+                let ranking = match (self, combo) {
+                    (RuleSet::ShortDeck, HandCombo::Flush(_)) => 7,
+                    (RuleSet::ShortDeck, HandCombo::FullHouse(_)) => 6,
+                    _ => combo.ranking(),
+                };
+
+                let mut values: Vec<u8> = combo_values(combo).iter().map(value_ranking).collect();
+                if !matches!(combo, HandCombo::FullHouse(_)) {
+                    values.sort_unstable_by(|a, b| b.cmp(a));
+                }
+
+                (ranking, values)
+            })
+            .collect();
+
+        // the kickers are the high cards ending the combos, and the ace might not be the highest one
+        let first_kicker = key
+            .iter()
+            .position(|(ranking, _)| *ranking == HandCombo::HighCard(Value::Two).ranking())
+            .unwrap_or(key.len());
+        key[first_kicker..].sort_unstable_by(|a, b| b.cmp(a));
+
+        key
+    }
+}
+
+fn combo_values(combo: &HandCombo) -> Vec<Value> {
+    match combo {
+        HandCombo::RoyalFlush => vec![Value::Ace],
+        HandCombo::FiveOfAKind(value)
+        | HandCombo::StraightFlush(value)
+        | HandCombo::FourOfAKind(value)
+        | HandCombo::Straight(value)
+        | HandCombo::ThreeOfAKind(value)
+        | HandCombo::Pair(value)
+        | HandCombo::HighCard(value) => vec![*value],
+        HandCombo::FullHouse(values) | HandCombo::TwoPairs(values) => values.to_vec(),
+        HandCombo::Flush(values) => values.to_vec(),
+    }
+}
+
+/// Given the cards of every player and the cards on the board (possibly none), return the hands
+/// which win with the given rules.
+///
+/// Every player makes their best five-card hand out of their cards and the board.
+/// Hands which cannot be parsed, which hold a card of the board, or which cannot make a five-card
+/// hand, are not taken into account.
+pub fn winning_hands_with_rules<'a>(
+    hands: &[&'a str],
+    board: &str,
+    rules: RuleSet,
+) -> Vec<&'a str> {
+    let board = match parse_cards(board) {
+        Ok(board) => board,
+        Err(_) => return vec![],
+    };
+
+    winners_by(
+        hands.iter().filter_map(|hand| {
+            Some((
+                *hand,
+                rules.best_combos(&parse_cards_after(hand, &board).ok()?, &board)?,
+            ))
+        }),
+        |a, b| Some(rules.compare(a, b)),
+    )
+}
+
+#[test]
+fn ace_to_five_kickers_have_a_low_ace() {
+    let rules = RuleSet::AceToFiveLowball;
+    let seven_high = rules.combos("7S 5C 4H 3D AC".parse().unwrap());
+    let six_high = rules.combos("6S 5C 4H 3D 2C".parse().unwrap());
+
+    assert_eq!(rules.compare(&six_high, &seven_high), Ordering::Greater);
+}
+
+#[test]
+fn short_deck_flush_beats_full_house() {
+    let rules = RuleSet::ShortDeck;
+
+    assert_eq!(
+        rules.compare(
+            &[HandCombo::Flush([
+                Value::King,
+                Value::Jack,
+                Value::Nine,
+                Value::Eight,
+                Value::Six
+            ])],
+            &[HandCombo::FullHouse([Value::Ace, Value::King])]
+        ),
+        Ordering::Greater
+    );
+}
@@ -0,0 +1,435 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::holdem::BestHand;
+use crate::pot::{side_pots, Pot};
+use crate::{winners_by_combos, Deck, PokerCard, Street};
+
+/// A player sitting at a table.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TablePlayer {
+    pub name: String,
+    pub stack: u32,
+}
+
+/// What the player whose turn it is can do. `Raise` gives the total the player bets on the street,
+/// and is also how the first bet of a street is made.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TableAction {
+    Fold,
+    Check,
+    Call,
+    Raise(u32),
+    AllIn,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TableError {
+    NotEnoughPlayers,
+    HandInProgress,
+    NoHandInProgress,
+    NotYourTurn,
+    CannotCheck,
+    NothingToCall,
+    RaiseTooSmall,
+    NotEnoughChips,
+    BettingNotReopened,
+    TableFull,
+}
+
+/// How the pots of a finished hand were won.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HandResult {
+    pub board: Vec<PokerCard>,
+    pub pots: Vec<(Pot, Vec<usize>)>, // every pot with the seats which won it
+}
+
+/// A Texas Hold'em table: the players sit in seat order, and the hands are dealt from decks
+/// shuffled with the seed of the table, so that the same actions always lead to the same hands.
+#[derive(Clone, Debug)]
+pub struct Table {
+    players: Vec<TablePlayer>,
+    small_blind: u32,
+    big_blind: u32,
+    seed: u64,
+    button: usize,
+    hands_played: u64,
+    hand: Option<HandState>,
+    last_result: Option<HandResult>,
+}
+
+#[derive(Clone, Debug)]
+struct HandState {
+    deck: Deck,
+    street: Street,
+    board: Vec<PokerCard>,
+    hole_cards: Vec<Vec<PokerCard>>,
+    in_hand: Vec<bool>, // dealt in and not folded
+    acted: Vec<bool>,
+    street_bets: Vec<u32>,
+    contributions: Vec<u32>,
+    current_bet: u32,
+    min_raise: u32,
+    to_act: usize,
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            TableError::NotEnoughPlayers => "at least two players with chips are needed",
+            TableError::HandInProgress => "a hand is already being played",
+            TableError::NoHandInProgress => "no hand is being played",
+            TableError::NotYourTurn => "it is not this player's turn",
+            TableError::CannotCheck => "cannot check when facing a bet",
+            TableError::NothingToCall => "there is no bet to call",
+            TableError::RaiseTooSmall => "the raise is smaller than the minimum raise",
+            TableError::NotEnoughChips => "the player does not have enough chips",
+            TableError::BettingNotReopened => "a short all in does not reopen the betting",
+            TableError::TableFull => "the table has no seat left",
+        };
+
+        write!(f, "{}", message)
+    }
+}
+
+impl Error for TableError {}
+
+impl Table {
+    /// The most players a deck can deal to: two hole cards each, and the five cards of the board.
+    pub const MAX_PLAYERS: usize = 23;
+
+    pub fn new(small_blind: u32, big_blind: u32, seed: u64) -> Self {
+        Table {
+            players: vec![],
+            small_blind,
+            big_blind,
+            seed,
+            button: 0,
+            hands_played: 0,
+            hand: None,
+            last_result: None,
+        }
+    }
+
+    /// Seats a new player after the others, returning their seat.
+    pub fn seat(&mut self, name: &str, stack: u32) -> Result<usize, TableError> {
+        if self.hand.is_some() {
+            return Err(TableError::HandInProgress);
+        }
+        if self.players.len() >= Table::MAX_PLAYERS {
+            return Err(TableError::TableFull);
+        }
+
+        self.players.push(TablePlayer {
+            name: name.to_string(),
+            stack,
+        });
+        Ok(self.players.len() - 1)
+    }
+
+    pub fn players(&self) -> &[TablePlayer] {
+        &self.players
+    }
+
+    pub fn button(&self) -> usize {
+        self.button
+    }
+
+    /// The seat of the player who must act, if a hand is being played.
+    pub fn to_act(&self) -> Option<usize> {
+        self.hand.as_ref().map(|hand| hand.to_act)
+    }
+
+    pub fn street(&self) -> Option<Street> {
+        self.hand.as_ref().map(|hand| hand.street)
+    }
+
+    pub fn board(&self) -> &[PokerCard] {
+        self.hand.as_ref().map_or(&[], |hand| &hand.board)
+    }
+
+    pub fn hole_cards(&self, seat: usize) -> &[PokerCard] {
+        self.hand
+            .as_ref()
+            .map_or(&[], |hand| &hand.hole_cards[seat])
+    }
+
+    /// The amount a player must reach to stay in the hand on the current street.
+    pub fn current_bet(&self) -> u32 {
+        self.hand.as_ref().map_or(0, |hand| hand.current_bet)
+    }
+
+    /// The chips put in the pots during the current hand.
+    pub fn pot(&self) -> u32 {
+        self.hand
+            .as_ref()
+            .map_or(0, |hand| hand.contributions.iter().sum())
+    }
+
+    pub fn last_result(&self) -> Option<&HandResult> {
+        self.last_result.as_ref()
+    }
+
+    /// Moves the button, posts the blinds and deals the hole cards of a new hand.
+    ///
+    /// Players without chips are not dealt in.
+    pub fn start_hand(&mut self) -> Result<(), TableError> {
+        if self.hand.is_some() {
+            return Err(TableError::HandInProgress);
+        }
+
+        let dealt_in: Vec<bool> = self.players.iter().map(|player| player.stack > 0).collect();
+        match dealt_in.iter().filter(|&&dealt_in| dealt_in).count() {
+            0 | 1 => return Err(TableError::NotEnoughPlayers),
+            players if players > Table::MAX_PLAYERS => return Err(TableError::TableFull),
+            _ => {}
+        }
+
+        if self.hands_played > 0 || !dealt_in[self.button] {
+            self.button = next_seat(self.button, &dealt_in);
+        }
+
+        let mut deck = Deck::shuffled(self.seed.wrapping_add(self.hands_played));
+        let hole_cards: Vec<Vec<PokerCard>> = dealt_in
+            .iter()
+            .map(|&dealt_in| match dealt_in {
+                true => deck.deal_many(2).unwrap(), // the unwrap is safe because at most `MAX_PLAYERS` players are dealt in
+                false => vec![],
+            })
+            .collect();
+
+        let mut hand = HandState {
+            deck,
+            street: Street::Preflop,
+            board: vec![],
+            hole_cards,
+            in_hand: dealt_in.clone(),
+            acted: vec![false; self.players.len()],
+            street_bets: vec![0; self.players.len()],
+            contributions: vec![0; self.players.len()],
+            current_bet: 0,
+            min_raise: self.big_blind,
+            to_act: self.button,
+        };
+
+        // heads up, the button posts the small blind and acts first before the flop
+        let small_blind_seat = match dealt_in.iter().filter(|&&dealt_in| dealt_in).count() {
+            2 => self.button,
+            _ => next_seat(self.button, &dealt_in),
+        };
+        let big_blind_seat = next_seat(small_blind_seat, &dealt_in);
+
+        self.put_in(&mut hand, small_blind_seat, self.small_blind);
+        self.put_in(&mut hand, big_blind_seat, self.big_blind);
+        hand.current_bet = self.big_blind;
+        hand.to_act = big_blind_seat;
+
+        self.hand = Some(hand);
+        self.next_turn();
+
+        Ok(())
+    }
+
+    /// Plays an action for the given seat, which must be the one to act.
+    pub fn act(&mut self, seat: usize, action: TableAction) -> Result<(), TableError> {
+        let mut hand = self.hand.take().ok_or(TableError::NoHandInProgress)?;
+        let result = self.apply(&mut hand, seat, action);
+        self.hand = Some(hand);
+
+        if result.is_ok() {
+            self.next_turn();
+        }
+        result
+    }
+
+    fn apply(
+        &mut self,
+        hand: &mut HandState,
+        seat: usize,
+        action: TableAction,
+    ) -> Result<(), TableError> {
+        if seat != hand.to_act {
+            return Err(TableError::NotYourTurn);
+        }
+
+        let stack = self.players[seat].stack;
+        let street_bet = hand.street_bets[seat];
+
+        match action {
+            TableAction::Fold => hand.in_hand[seat] = false,
+            TableAction::Check => {
+                if street_bet < hand.current_bet {
+                    return Err(TableError::CannotCheck);
+                }
+            }
+            TableAction::Call => {
+                if street_bet >= hand.current_bet {
+                    return Err(TableError::NothingToCall);
+                }
+                self.put_in(hand, seat, hand.current_bet - street_bet);
+            }
+            TableAction::Raise(total) => {
+                if hand.acted[seat] {
+                    return Err(TableError::BettingNotReopened);
+                }
+                if total < hand.current_bet + hand.min_raise {
+                    return Err(TableError::RaiseTooSmall);
+                }
+                if total - street_bet > stack {
+                    return Err(TableError::NotEnoughChips);
+                }
+                self.raise_to(hand, seat, total);
+            }
+            TableAction::AllIn => {
+                let total = street_bet + stack;
+                if total > hand.current_bet && hand.acted[seat] {
+                    return Err(TableError::BettingNotReopened);
+                }
+                if total > hand.current_bet {
+                    self.raise_to(hand, seat, total);
+                } else {
+                    self.put_in(hand, seat, stack);
+                }
+            }
+        }
+
+        hand.acted[seat] = true;
+        Ok(())
+    }
+
+    fn raise_to(&mut self, hand: &mut HandState, seat: usize, total: u32) {
+        // a raise smaller than the minimum can only happen when going all in,
+        // and it does not change the minimum raise
+        let full_raise = total - hand.current_bet >= hand.min_raise;
+        hand.min_raise = hand.min_raise.max(total - hand.current_bet);
+        hand.current_bet = total;
+        self.put_in(hand, seat, total - hand.street_bets[seat]);
+
+        // everybody can raise again after a full raise, but the players who already acted
+        // can only call or fold a short all in
+        if full_raise {
+            hand.acted = vec![false; self.players.len()];
+        }
+    }
+
+    fn put_in(&mut self, hand: &mut HandState, seat: usize, amount: u32) {
+        let amount = amount.min(self.players[seat].stack);
+
+        self.players[seat].stack -= amount;
+        hand.street_bets[seat] += amount;
+        hand.contributions[seat] += amount;
+    }
+
+    // finds who acts next, dealing the next streets and ending the hand when the betting is over
+    fn next_turn(&mut self) {
+        loop {
+            let hand = match &mut self.hand {
+                Some(hand) => hand,
+                None => return,
+            };
+
+            if hand.in_hand.iter().filter(|&&in_hand| in_hand).count() == 1 {
+                return self.finish_hand();
+            }
+
+            let can_act: Vec<bool> = (0..self.players.len())
+                .map(|seat| hand.in_hand[seat] && self.players[seat].stack > 0)
+                .collect();
+            let must_act = |seat: usize| {
+                can_act[seat] && (!hand.acted[seat] || hand.street_bets[seat] < hand.current_bet)
+            };
+
+            // nobody needs to act when everybody but one player is all in and that player matched the bet
+            let betting_over = can_act.iter().filter(|&&can_act| can_act).count() <= 1
+                && (0..self.players.len())
+                    .all(|seat| !can_act[seat] || hand.street_bets[seat] >= hand.current_bet);
+
+            if !betting_over {
+                if let Some(seat) = (1..=self.players.len())
+                    .map(|offset| (hand.to_act + offset) % self.players.len())
+                    .find(|&seat| must_act(seat))
+                {
+                    hand.to_act = seat;
+                    return;
+                }
+            }
+
+            if hand.street == Street::River {
+                return self.finish_hand();
+            }
+
+            let (street, cards) = match hand.street {
+                Street::Preflop => (Street::Flop, 3),
+                Street::Flop => (Street::Turn, 1),
+                _ => (Street::River, 1),
+            };
+            let dealt = hand.deck.deal_many(cards).unwrap(); // the unwrap is safe because the hole cards of at most `MAX_PLAYERS` players leave enough cards for the board
+
+            hand.street = street;
+            hand.board.extend(dealt);
+            hand.street_bets = vec![0; self.players.len()];
+            hand.acted = vec![false; self.players.len()];
+            hand.current_bet = 0;
+            hand.min_raise = self.big_blind;
+            // after the flop, the first player after the button acts first
+            hand.to_act = self.button;
+        }
+    }
+
+    fn finish_hand(&mut self) {
+        let hand = match self.hand.take() {
+            Some(hand) => hand,
+            None => return,
+        };
+
+        let folded: Vec<bool> = hand.in_hand.iter().map(|&in_hand| !in_hand).collect();
+        let players_left = hand.in_hand.iter().filter(|&&in_hand| in_hand).count();
+
+        let mut pots: Vec<(Pot, Vec<usize>)> = vec![];
+        for pot in side_pots(&hand.contributions, &folded) {
+            let winners = match players_left {
+                1 => pot.eligible.clone(),
+                _ => showdown(&pot.eligible, &hand),
+            };
+
+            for (seat, amount) in pot.split(&winners) {
+                self.players[seat].stack += amount;
+            }
+            pots.push((pot, winners));
+        }
+
+        self.last_result = Some(HandResult {
+            board: hand.board,
+            pots,
+        });
+        self.hands_played += 1;
+    }
+}
+
+// the seats of the players with the best hands, in seat order
+fn showdown(eligible: &[usize], hand: &HandState) -> Vec<usize> {
+    winners_by_combos(eligible.iter().map(|&seat| {
+        let cards: Vec<PokerCard> = hand.hole_cards[seat]
+            .iter()
+            .chain(hand.board.iter())
+            .cloned()
+            .collect();
+
+        // the unwrap is safe because the board is complete at showdown
+        (seat, BestHand::find(&cards).unwrap().into_combos())
+    }))
+}
+
+// the first seat after the given one which is dealt in
+fn next_seat(seat: usize, dealt_in: &[bool]) -> usize {
+    (1..=dealt_in.len())
+        .map(|offset| (seat + offset) % dealt_in.len())
+        .find(|&seat| dealt_in[seat])
+        .unwrap_or(seat)
+}
+
+#[test]
+fn next_seat_skips_players_not_dealt_in() {
+    assert_eq!(next_seat(0, &[true, false, true]), 2);
+    assert_eq!(next_seat(2, &[true, false, true]), 0);
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::holdem::card_texts;
+use crate::{
+    winners_by_combos, CardPosition, Color, HandCombo, PokerCard, PokerHand,
+    PokerHandFromStrConversionError, Value,
+};
+
+/// A card of a hand played with wild cards: either a regular card, or a joker written `"JK"`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum WildCard {
+    Card(PokerCard),
+    Joker,
+}
+
+/// Five cards which may contain jokers, written separated by spaces, like `"2S JK 7H AC JK"`.
+/// Only the jokers may appear more than once.
+#[derive(Clone, PartialEq, Debug)]
+pub struct WildHand([WildCard; 5]);
+
+/// Which cards can stand for any other card: jokers always can, and so can every card
+/// whose value is one of the wild values.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct WildRules {
+    wild_values: Vec<Value>,
+}
+
+impl FromStr for WildCard {
+    type Err = PokerHandFromStrConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "JK" | "jk" => Ok(WildCard::Joker),
+            _ => Ok(WildCard::Card(PokerCard::from_str(s)?)),
+        }
+    }
+}
+
+impl FromStr for WildHand {
+    type Err = PokerHandFromStrConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cards: Vec<WildCard> = vec![];
+
+        // the jokers are the only cards which can appear several times
+        for (index, (offset, text)) in card_texts(s).enumerate() {
+            let card = WildCard::from_str(text).map_err(|error| error.at(index, offset))?;
+
+            if card != WildCard::Joker && cards.contains(&card) {
+                return Err(PokerHandFromStrConversionError::DuplicateCard(
+                    CardPosition {
+                        index,
+                        offset,
+                        text: text.to_string(),
+                    },
+                ));
+            }
+            cards.push(card);
+        }
+
+        <[WildCard; 5]>::try_from(cards)
+            .map(WildHand)
+            .map_err(|cards| PokerHandFromStrConversionError::CardsAmountError(cards.len()))
+    }
+}
+
+impl fmt::Display for WildCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WildCard::Card(card) => write!(f, "{}", card),
+            WildCard::Joker => write!(f, "JK"),
+        }
+    }
+}
+
+impl fmt::Display for WildHand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cards: Vec<String> = self.0.iter().map(WildCard::to_string).collect();
+        write!(f, "{}", cards.join(" "))
+    }
+}
+
+impl WildRules {
+    /// Only the jokers are wild.
+    pub fn new() -> Self {
+        WildRules::default()
+    }
+
+    /// The jokers and the twos are wild.
+    pub fn deuces_wild() -> Self {
+        WildRules::new().with_wild_value(Value::Two)
+    }
+
+    /// Makes every card of the given value wild as well.
+    pub fn with_wild_value(mut self, value: Value) -> Self {
+        if !self.wild_values.contains(&value) {
+            self.wild_values.push(value);
+        }
+        self
+    }
+
+    pub fn is_wild(&self, card: &WildCard) -> bool {
+        match card {
+            WildCard::Card(card) => self.wild_values.contains(&card.value),
+            WildCard::Joker => true,
+        }
+    }
+}
+
+impl WildHand {
+    pub fn new(cards: [WildCard; 5]) -> Self {
+        WildHand(cards)
+    }
+
+    pub fn cards(&self) -> &[WildCard; 5] {
+        &self.0
+    }
+
+    /// The combos of the best hand the wild cards can make, from the best one to the worst one.
+    pub fn combos(&self, rules: &WildRules) -> Vec<HandCombo> {
+        let natural_cards: Vec<PokerCard> = self
+            .0
+            .iter()
+            .filter(|card| !rules.is_wild(card))
+            .map(|card| match card {
+                WildCard::Card(card) => card.clone(),
+                WildCard::Joker => unreachable!(), // jokers are always wild
+            })
+            .collect();
+
+        // the colors of the wild cards only matter to complete a flush, which is never worse than
+        // what the same values would make without it, but a wild card cannot stand for a card
+        // already in the hand: when the values need one, they are played in another color
+        let flush_color = natural_cards
+            .first()
+            .map_or(Color::Spade, |card| card.color);
+        let other_color = match flush_color {
+            Color::Spade => Color::Heart,
+            _ => Color::Spade,
+        };
+
+        let mut best_combos: Vec<HandCombo> = vec![];
+
+        for values in values_with_repetition(5 - natural_cards.len()) {
+            // the values are sorted, so that the repeated ones are next to each other
+            let can_flush = values.windows(2).all(|pair| pair[0] != pair[1])
+                && values
+                    .iter()
+                    .all(|&value| !natural_cards.contains(&PokerCard::new(value, flush_color)));
+            let color = if can_flush { flush_color } else { other_color };
+
+            let mut cards = natural_cards.clone();
+            cards.extend(values.into_iter().map(|value| PokerCard::new(value, color)));
+
+            // the unwrap is safe because the wild cards complete the hand to 5 cards
+            let combos = PokerHand(<[PokerCard; 5]>::try_from(cards).unwrap()).find_combos();
+
+            if combos > best_combos {
+                best_combos = combos;
+            }
+        }
+
+        best_combos
+    }
+}
+
+// every way of picking `amount` values when a value can be picked several times,
+// each of them sorted from the lowest value to the highest one
+fn values_with_repetition(amount: usize) -> Vec<Vec<Value>> {
+    let mut picks: Vec<Vec<Value>> = vec![vec![]];
+
+    for _ in 0..amount {
+        picks = picks
+            .into_iter()
+            .flat_map(|pick| {
+                let lowest = pick.last().copied().unwrap_or(Value::Two);
+
+                Value::ALL
+                    .iter()
+                    .filter(move |&&value| value >= lowest)
+                    .map(move |&value| {
+                        let mut pick = pick.clone();
+                        pick.push(value);
+                        pick
+                    })
+            })
+            .collect();
+    }
+
+    picks
+}
+
+/// Given a list of poker hands which may contain jokers, return the hands which win
+/// when playing with the given wild cards.
+///
+/// Hands which cannot be parsed are not taken into account.
+pub fn winning_wild_hands<'a>(hands: &[&'a str], rules: &WildRules) -> Vec<&'a str> {
+    winners_by_combos(
+        hands
+            .iter()
+            .filter_map(|hand| Some((*hand, hand.parse::<WildHand>().ok()?.combos(rules)))),
+    )
+}
+
+#[test]
+fn values_with_repetition_amounts() {
+    assert_eq!(values_with_repetition(0), vec![vec![]]);
+    assert_eq!(values_with_repetition(1).len(), 13);
+    assert_eq!(values_with_repetition(2).len(), 91);
+}
+
+#[test]
+fn joker_completes_a_straight_flush() {
+    let hand: WildHand = "9H JK JH QH KH".parse().unwrap();
+
+    assert_eq!(
+        hand.combos(&WildRules::new()),
+        vec![HandCombo::StraightFlush(Value::King)]
+    );
+}
+
+#[test]
+fn deuces_make_five_of_a_kind() {
+    let hand: WildHand = "2S 7H 2D 7C JK".parse().unwrap();
+
+    assert_eq!(
+        hand.combos(&WildRules::deuces_wild()),
+        vec![HandCombo::FiveOfAKind(Value::Seven)]
+    );
+}
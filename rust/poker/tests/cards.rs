@@ -0,0 +1,200 @@
+use poker::{
+    try_winning_hands, CardPosition, Color, Deck, HandCombo, InvalidHandError, PokerCard,
+    PokerHand, PokerHandFromStrConversionError, Value,
+};
+use std::collections::HashSet;
+
+#[test]
+fn test_hand_display_round_trips() {
+    let hand: PokerHand = "2S 4S 7H AC JH".parse().unwrap();
+
+    assert_eq!(hand.to_string(), "2S 4S 7H AC JH");
+    assert_eq!(hand.to_string().parse::<PokerHand>(), Ok(hand));
+}
+
+#[test]
+fn test_ten_is_written_t() {
+    assert_eq!(PokerCard::new(Value::Ten, Color::Diamond).to_string(), "TD");
+}
+
+#[test]
+fn test_cards_accessors() {
+    let card: PokerCard = "QC".parse().unwrap();
+
+    assert_eq!(card.value(), Value::Queen);
+    assert_eq!(card.color(), Color::Club);
+}
+
+#[test]
+fn test_cards_are_ordered_by_value_then_color() {
+    let mut cards: Vec<PokerCard> = ["KH", "2C", "KS", "9D"]
+        .iter()
+        .map(|card| card.parse().unwrap())
+        .collect();
+    cards.sort();
+
+    assert_eq!(
+        cards
+            .iter()
+            .map(PokerCard::to_string)
+            .collect::<Vec<String>>(),
+        vec!["2C", "9D", "KS", "KH"]
+    );
+}
+
+#[test]
+fn test_cards_can_be_hashed() {
+    let cards: HashSet<PokerCard> = ["AS", "AS", "AH"]
+        .iter()
+        .map(|card| card.parse().unwrap())
+        .collect();
+
+    assert_eq!(cards.len(), 2);
+}
+
+#[test]
+fn test_hand_combos() {
+    let hand: PokerHand = "8S 8D 3H 8C 3S".parse().unwrap();
+
+    assert_eq!(
+        hand.combos(),
+        vec![HandCombo::FullHouse([Value::Eight, Value::Three])]
+    );
+}
+
+#[test]
+fn test_conversion_error_is_an_error() {
+    let error: Box<dyn std::error::Error> =
+        Box::new("2S 4S 7H AC".parse::<PokerHand>().unwrap_err());
+
+    assert_eq!(error.to_string(), "a hand must have 5 cards, found 4");
+    assert_eq!(
+        "ZH".parse::<PokerCard>(),
+        Err(PokerHandFromStrConversionError::UnknownValue(
+            CardPosition {
+                index: 0,
+                offset: 0,
+                text: "ZH".to_string()
+            }
+        ))
+    );
+}
+
+#[test]
+fn test_conversion_error_tells_where_the_card_is() {
+    let error = "2S  4S 7H AC ZH".parse::<PokerHand>().unwrap_err();
+
+    assert_eq!(
+        error.position(),
+        Some(&CardPosition {
+            index: 4,
+            offset: 13,
+            text: "ZH".to_string()
+        })
+    );
+    assert_eq!(
+        error.to_string(),
+        "unknown card value in \"ZH\" (card 4 at byte 13)"
+    );
+}
+
+#[test]
+fn test_ten_and_lowercase_cards_can_be_parsed() {
+    let hand: PokerHand = "10h jh qh kh ah".parse().unwrap();
+
+    assert_eq!(hand.to_string(), "TH JH QH KH AH");
+    assert_eq!(hand.combos(), vec![HandCombo::RoyalFlush]);
+    assert_eq!(
+        "10".parse::<PokerCard>(),
+        Err(PokerHandFromStrConversionError::CardLenError(
+            CardPosition {
+                index: 0,
+                offset: 0,
+                text: "10".to_string()
+            }
+        ))
+    );
+}
+
+#[test]
+fn test_duplicate_cards_in_a_hand() {
+    assert_eq!(
+        "2S 4S 7H 4S JH".parse::<PokerHand>(),
+        Err(PokerHandFromStrConversionError::DuplicateCard(
+            CardPosition {
+                index: 3,
+                offset: 9,
+                text: "4S".to_string()
+            }
+        ))
+    );
+}
+
+#[test]
+fn test_duplicate_cards_across_hands() {
+    assert_eq!(
+        try_winning_hands(&["2S 4S 7H AC JH", "3D 5C 9H JH KD"]),
+        Err(InvalidHandError {
+            hand: 1,
+            error: PokerHandFromStrConversionError::DuplicateCard(CardPosition {
+                index: 3,
+                offset: 9,
+                text: "JH".to_string()
+            })
+        })
+    );
+    assert_eq!(
+        try_winning_hands(&["2S 4S 7H AC JH", "3D 5C 9H JC KD"]),
+        Ok(vec!["2S 4S 7H AC JH"])
+    );
+}
+
+#[test]
+fn test_invalid_hand_among_hands() {
+    let error = try_winning_hands(&["2S 4S 7H AC JH", "3D 5C"]).unwrap_err();
+
+    assert_eq!(error.hand, 1);
+    assert_eq!(
+        error.to_string(),
+        "hand 1: a hand must have 5 cards, found 2"
+    );
+}
+
+#[test]
+fn test_new_deck_has_every_card_once() {
+    let deck = Deck::new();
+    let cards: HashSet<&PokerCard> = deck.cards().collect();
+
+    assert_eq!(deck.len(), 52);
+    assert_eq!(cards.len(), 52);
+}
+
+#[test]
+fn test_shuffle_is_seeded() {
+    assert_eq!(Deck::shuffled(3), Deck::shuffled(3));
+    assert_ne!(Deck::shuffled(3), Deck::shuffled(4));
+    assert_ne!(Deck::shuffled(3), Deck::new());
+}
+
+#[test]
+fn test_dealing_takes_cards_from_the_top() {
+    let mut deck = Deck::shuffled(11);
+    let top: Vec<PokerCard> = deck.cards().take(3).cloned().collect();
+
+    assert_eq!(deck.deal(), Some(top[0].clone()));
+    assert_eq!(deck.deal_many(2), Some(top[1..].to_vec()));
+    assert_eq!(deck.len(), 49);
+    assert_eq!(deck.deal_many(50), None);
+    assert_eq!(deck.len(), 49);
+}
+
+#[test]
+fn test_removed_cards_are_not_dealt() {
+    let mut deck = Deck::new();
+    let ace: PokerCard = "AS".parse().unwrap();
+
+    assert!(deck.remove(&ace));
+    assert!(!deck.remove(&ace));
+    assert!(deck.deal_many(51).unwrap().iter().all(|card| card != &ace));
+    assert!(deck.is_empty());
+}
@@ -0,0 +1,87 @@
+use poker::{equity, Equity, EquityMethod};
+
+fn assert_equity(actual: Equity, win: f64, tie: f64, loss: f64) {
+    let close = |a: f64, b: f64| (a - b).abs() < 0.01;
+
+    assert!(
+        close(actual.win, win) && close(actual.tie, tie) && close(actual.loss, loss),
+        "expected {win}/{tie}/{loss}, got {actual:?}"
+    )
+}
+
+#[test]
+fn test_complete_board_is_decided() {
+    let equities = equity(
+        &["AS AD", "KS KD"],
+        "2C 7H 9D JC 3S",
+        EquityMethod::Exhaustive,
+    )
+    .unwrap();
+
+    assert_equity(equities[0], 100.0, 0.0, 0.0);
+    assert_equity(equities[1], 0.0, 0.0, 100.0);
+}
+
+#[test]
+fn test_exhaustive_river_outs() {
+    // only the two remaining kings save the second player out of the 44 unseen cards
+    let equities = equity(&["AS AD", "KS KD"], "2C 7H 9D JC", EquityMethod::Exhaustive).unwrap();
+
+    assert_equity(equities[0], 4200.0 / 44.0, 0.0, 200.0 / 44.0);
+    assert_equity(equities[1], 200.0 / 44.0, 0.0, 4200.0 / 44.0);
+}
+
+#[test]
+fn test_exhaustive_split_pot() {
+    let equities = equity(&["2S 3D", "2D 3S"], "AH KH QD JC", EquityMethod::Exhaustive).unwrap();
+
+    assert_equity(equities[0], 0.0, 100.0, 0.0);
+    assert_equity(equities[1], 0.0, 100.0, 0.0);
+}
+
+#[test]
+fn test_monte_carlo_is_seeded() {
+    let method = EquityMethod::MonteCarlo {
+        samples: 500,
+        seed: 42,
+    };
+
+    assert_eq!(
+        equity(&["AS AD", "7C 2D"], "", method),
+        equity(&["AS AD", "7C 2D"], "", method)
+    );
+}
+
+#[test]
+fn test_monte_carlo_percentages() {
+    let method = EquityMethod::MonteCarlo {
+        samples: 2000,
+        seed: 7,
+    };
+    let equities = equity(&["AS AD", "7C 2D"], "", method).unwrap();
+
+    // pocket aces are roughly an 88% favourite against seven-deuce
+    assert!(equities[0].win > 80.0 && equities[0].win < 95.0);
+    assert_equity(
+        equities[1],
+        100.0 - equities[0].win - equities[0].tie,
+        equities[0].tie,
+        equities[0].win,
+    );
+}
+
+#[test]
+fn test_duplicate_card_is_rejected() {
+    assert_eq!(
+        equity(&["AS AD", "AS KD"], "", EquityMethod::Exhaustive),
+        None
+    );
+}
+
+#[test]
+fn test_too_many_board_cards_is_rejected() {
+    assert_eq!(
+        equity(&["AS AD"], "2C 3C 4C 5C 6C 7C", EquityMethod::Exhaustive),
+        None
+    );
+}
@@ -0,0 +1,73 @@
+use poker::{fast_rank, Deck, PokerCard, PokerHand};
+
+fn cards(s: &str) -> Vec<PokerCard> {
+    s.split_whitespace()
+        .map(|card| card.parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_royal_flush_is_the_best_rank() {
+    assert_eq!(fast_rank(&cards("AS KS QS JS TS")), Some(7462));
+}
+
+#[test]
+fn test_worst_high_card_is_the_lowest_rank() {
+    assert_eq!(fast_rank(&cards("7D 5S 4C 3H 2H")), Some(1));
+}
+
+#[test]
+fn test_colors_do_not_matter_without_a_flush() {
+    assert_eq!(
+        fast_rank(&cards("9S 9D 4C 4H KH")),
+        fast_rank(&cards("9H 9C 4S 4D KS"))
+    );
+}
+
+#[test]
+fn test_seven_cards_use_the_best_five() {
+    assert_eq!(
+        fast_rank(&cards("2C 8H 8D 8S KH QD 2D")),
+        fast_rank(&cards("8H 8D 8S 2C 2D"))
+    );
+}
+
+#[test]
+fn test_not_enough_cards() {
+    assert_eq!(fast_rank(&cards("2C 8H 8D 8S")), None);
+}
+
+#[test]
+fn test_agrees_with_hand_combos() {
+    for seed in 0..300 {
+        let mut deck = Deck::shuffled(seed);
+        let a = PokerHand::new(deck.deal_many(5).unwrap().try_into().unwrap());
+        let b = PokerHand::new(deck.deal_many(5).unwrap().try_into().unwrap());
+
+        assert_eq!(
+            fast_rank(a.cards()).cmp(&fast_rank(b.cards())),
+            a.combos().partial_cmp(&b.combos()).unwrap(),
+            "{a} against {b}"
+        );
+    }
+}
+
+#[test]
+fn test_duplicate_cards() {
+    assert_eq!(fast_rank(&cards("AS AH AD AC AS")), None);
+    assert_eq!(fast_rank(&cards("AS AS AS AS AS")), None);
+    assert_eq!(fast_rank(&cards("AS KS QS JS TS AS 2D")), None);
+}
+
+#[test]
+fn test_more_than_seven_cards() {
+    assert_eq!(fast_rank(&cards("2D AS KS 3C QS JS 4H TS")), Some(7462));
+    assert_eq!(
+        fast_rank(&cards("2S 3D 5H 7C 9S JD KH 4C")),
+        fast_rank(&cards("KH JD 9S 7C 5H"))
+    );
+
+    let mut too_many: Vec<PokerCard> = Deck::new().cards().cloned().collect();
+    too_many.push(too_many[0].clone());
+    assert_eq!(fast_rank(&too_many), None);
+}
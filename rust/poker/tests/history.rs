@@ -0,0 +1,232 @@
+use poker::{Action, HandHistory, HandHistoryError, Street};
+
+const FOLDED_ON_THE_RIVER: &str = "
+Seat 1: alice (1000)
+Seat 2: bob (1000)
+Dealt to alice: AS KD
+*** PREFLOP ***
+alice posts small blind 5
+bob posts big blind 10
+alice raises to 30
+bob calls 20
+*** FLOP *** 9H 2S 7C
+bob checks
+alice bets 50
+bob calls 50
+*** TURN *** KS
+bob checks
+alice checks
+*** RIVER *** 3D
+bob bets 100
+alice folds
+";
+
+const THREE_WAY_ALL_IN: &str = "
+Seat 1: alice (1000)
+Seat 2: bob (1000)
+Seat 3: carol (300)
+*** PREFLOP ***
+alice posts small blind 5
+bob posts big blind 10
+carol raises to 300
+alice raises to 1000
+bob calls 990
+*** FLOP *** 9H 2S 7C
+*** TURN *** 4D
+*** RIVER *** 3D
+*** SHOWDOWN ***
+alice shows AS AD
+bob shows KH KD
+carol shows 7H 7S
+";
+
+#[test]
+fn test_parse_seats_and_streets() {
+    let history: HandHistory = FOLDED_ON_THE_RIVER.parse().unwrap();
+
+    assert_eq!(history.seats.len(), 2);
+    assert_eq!(history.seats[1].player, "bob");
+    assert_eq!(history.seats[1].stack, 1000);
+    assert_eq!(
+        history
+            .streets
+            .iter()
+            .map(|street| street.street)
+            .collect::<Vec<Street>>(),
+        vec![Street::Preflop, Street::Flop, Street::Turn, Street::River]
+    );
+    assert_eq!(history.streets[0].actions[2].action, Action::RaiseTo(30));
+    assert_eq!(history.board().len(), 5);
+}
+
+#[test]
+fn test_uncontested_pot() {
+    let replay = FOLDED_ON_THE_RIVER
+        .parse::<HandHistory>()
+        .unwrap()
+        .replay()
+        .unwrap();
+
+    assert_eq!(replay.total_pot(), 260);
+    assert_eq!(replay.pots[0].winners, vec!["bob"]);
+    assert_eq!(
+        replay.stacks,
+        vec![("alice".to_string(), 920), ("bob".to_string(), 1080)]
+    );
+}
+
+#[test]
+fn test_side_pots_at_showdown() {
+    let replay = THREE_WAY_ALL_IN
+        .parse::<HandHistory>()
+        .unwrap()
+        .replay()
+        .unwrap();
+
+    assert_eq!(replay.pots.len(), 2);
+    assert_eq!(replay.pots[0].amount, 900);
+    assert_eq!(replay.pots[0].winners, vec!["carol"]);
+    assert_eq!(replay.pots[1].amount, 1400);
+    assert_eq!(replay.pots[1].eligible, vec!["alice", "bob"]);
+    assert_eq!(replay.pots[1].winners, vec!["alice"]);
+    assert_eq!(
+        replay.stacks,
+        vec![
+            ("alice".to_string(), 1400),
+            ("bob".to_string(), 0),
+            ("carol".to_string(), 900)
+        ]
+    );
+}
+
+#[test]
+fn test_split_pot() {
+    let history: HandHistory = "
+Seat 1: alice (100)
+Seat 2: bob (100)
+*** PREFLOP ***
+alice posts 5
+bob posts 5
+*** FLOP *** AH KH QH
+*** TURN *** JH
+*** RIVER *** TH
+*** SHOWDOWN ***
+alice shows 2C 3D
+bob shows 4S 5S
+"
+    .parse()
+    .unwrap();
+    let replay = history.replay().unwrap();
+
+    assert_eq!(replay.pots[0].winners, vec!["alice", "bob"]);
+    assert_eq!(
+        replay.stacks,
+        vec![("alice".to_string(), 100), ("bob".to_string(), 100)]
+    );
+}
+
+#[test]
+fn test_invalid_card() {
+    assert!(matches!(
+        "Seat 1: alice (100)\nDealt to alice: AS KX".parse::<HandHistory>(),
+        Err(HandHistoryError::InvalidCard(2, _))
+    ));
+}
+
+#[test]
+fn test_invalid_line() {
+    assert_eq!(
+        "Seat 1: alice (100)\n*** PREFLOP ***\nalice dances".parse::<HandHistory>(),
+        Err(HandHistoryError::InvalidLine(3))
+    );
+}
+
+#[test]
+fn test_unknown_player() {
+    assert_eq!(
+        "Seat 1: alice (100)\n*** PREFLOP ***\nbob checks"
+            .parse::<HandHistory>()
+            .unwrap()
+            .replay(),
+        Err(HandHistoryError::UnknownPlayer(3, "bob".to_string()))
+    );
+}
+
+#[test]
+fn test_not_enough_chips() {
+    assert_eq!(
+        "Seat 1: alice (100)\n*** PREFLOP ***\nalice bets 150"
+            .parse::<HandHistory>()
+            .unwrap()
+            .replay(),
+        Err(HandHistoryError::NotEnoughChips(3, "alice".to_string()))
+    );
+}
+
+#[test]
+fn test_raise_below_the_bet() {
+    assert_eq!(
+        "Seat 1: alice (100)\nSeat 2: bob (100)\n*** PREFLOP ***\nalice bets 40\nbob raises to 30"
+            .parse::<HandHistory>()
+            .unwrap()
+            .replay(),
+        Err(HandHistoryError::RaiseTooSmall(5, "bob".to_string()))
+    );
+}
+
+#[test]
+fn test_folded_player_acted() {
+    assert_eq!(
+        "Seat 1: alice (100)\n*** PREFLOP ***\nalice folds\nalice checks"
+            .parse::<HandHistory>()
+            .unwrap()
+            .replay(),
+        Err(HandHistoryError::FoldedPlayerActed(4, "alice".to_string()))
+    );
+}
+
+#[test]
+fn test_showdown_without_cards() {
+    let history: HandHistory = "
+Seat 1: alice (100)
+Seat 2: bob (100)
+*** PREFLOP ***
+alice posts 5
+bob posts 5
+*** FLOP *** AH KH QH
+*** TURN *** JH
+*** RIVER *** 2D
+*** SHOWDOWN ***
+"
+    .parse()
+    .unwrap();
+
+    assert_eq!(history.replay(), Err(HandHistoryError::UnknownWinner));
+}
+
+#[test]
+fn test_uncontested_side_pot_goes_back_to_its_owner() {
+    let replay = "
+Seat 1: alice (1000)
+Seat 2: bob (300)
+*** PREFLOP ***
+alice raises to 1000
+bob calls 300
+*** FLOP *** 9H 2S 7C
+*** TURN *** 4D
+*** RIVER *** 3D
+*** SHOWDOWN ***
+bob shows KH KD
+"
+    .parse::<HandHistory>()
+    .unwrap()
+    .replay()
+    .unwrap();
+
+    assert_eq!(replay.pots[0].winners, vec!["bob"]);
+    assert_eq!(replay.pots[1].winners, vec!["alice"]);
+    assert_eq!(
+        replay.stacks,
+        vec![("alice".to_string(), 700), ("bob".to_string(), 600)]
+    );
+}
@@ -0,0 +1,67 @@
+use poker::{best_hand, winning_holdem_hands};
+
+#[test]
+fn test_best_hand_of_five_cards_is_the_hand() {
+    assert_eq!(
+        best_hand("4S 5S 7H 8D JC"),
+        Some(vec!["4S", "5S", "7H", "8D", "JC"])
+    )
+}
+
+#[test]
+fn test_best_hand_of_seven_cards() {
+    assert_eq!(
+        best_hand("KH 3S 9H 2H 5H QD JH"),
+        Some(vec!["KH", "9H", "2H", "5H", "JH"])
+    )
+}
+
+#[test]
+fn test_best_hand_of_six_cards_keeps_highest_kickers() {
+    assert_eq!(
+        best_hand("4D 4C 9S 2H KD 7C"),
+        Some(vec!["4D", "4C", "9S", "KD", "7C"])
+    )
+}
+
+#[test]
+fn test_best_hand_needs_five_cards() {
+    assert_eq!(best_hand("4D 4C 9S 2H"), None)
+}
+
+#[test]
+fn test_best_hand_invalid_card() {
+    assert_eq!(best_hand("4D 4C 9S 2H KX 7C"), None)
+}
+
+#[test]
+fn test_holdem_best_hand_wins() {
+    assert_eq!(
+        winning_holdem_hands(&["AS KD", "9C 9D"], "9H 2S 7C KS 3D"),
+        vec!["9C 9D"]
+    )
+}
+
+#[test]
+fn test_holdem_board_plays() {
+    assert_eq!(
+        winning_holdem_hands(&["2S 3D", "4C 2D"], "AH KH QH JH TH"),
+        vec!["2S 3D", "4C 2D"]
+    )
+}
+
+#[test]
+fn test_holdem_kicker_decides() {
+    assert_eq!(
+        winning_holdem_hands(&["AS QD", "AD JC"], "AH 8S 7C 4S 2D"),
+        vec!["AS QD"]
+    )
+}
+
+#[test]
+fn test_holdem_hole_cards_on_the_board_are_ignored() {
+    assert_eq!(
+        winning_holdem_hands(&["AS AH", "QC JC"], "AS KD 7C 2H 3D"),
+        vec!["QC JC"]
+    );
+}
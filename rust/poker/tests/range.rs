@@ -0,0 +1,123 @@
+use poker::{range_equity, EquityMethod, PokerCard, Range, RangeError};
+
+fn combos(range: &str) -> Vec<String> {
+    range
+        .parse::<Range>()
+        .unwrap()
+        .combos()
+        .iter()
+        .map(|combo| format!("{} {}", combo.cards[0], combo.cards[1]))
+        .collect()
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn test_amount_of_combos() {
+    let amount = |range: &str| range.parse::<Range>().unwrap().len();
+
+    assert_eq!(amount("TT"), 6);
+    assert_eq!(amount("AKs"), 4);
+    assert_eq!(amount("AKo"), 12);
+    assert_eq!(amount("AK"), 16);
+    assert_eq!(amount("TT+"), 30);
+    assert_eq!(amount("TT-77"), 24);
+    assert_eq!(amount("AQo+"), 24);
+    assert_eq!(amount("A2s+"), 48);
+    assert_eq!(amount("KTs-K7s"), 16);
+    assert_eq!(amount("76s-54s"), 12);
+    assert_eq!(amount("AKs, TT+, 76s-54s, AQo+"), 70);
+    assert_eq!(amount(""), 0);
+}
+
+#[test]
+fn test_suited_combos() {
+    assert_eq!(combos("AKs"), vec!["AS KS", "AD KD", "AH KH", "AC KC"]);
+}
+
+#[test]
+fn test_connectors_span() {
+    let combos = combos("76s-54s");
+
+    assert!(combos.contains(&"5S 4S".to_string()));
+    assert!(combos.contains(&"6H 5H".to_string()));
+    assert!(combos.contains(&"7C 6C".to_string()));
+}
+
+#[test]
+fn test_single_combo() {
+    assert_eq!(combos("AhKh, 7c2d"), vec!["AH KH", "7C 2D"]);
+}
+
+#[test]
+fn test_weights() {
+    let range: Range = "AKs:0.5, AK".parse().unwrap();
+
+    assert_eq!(range.len(), 16);
+    assert_eq!(range.combos()[0].weight, 0.5);
+    assert_eq!(range.combos()[15].weight, 1.0);
+    assert_eq!(range.total_weight(), 14.0);
+}
+
+#[test]
+fn test_invalid_ranges() {
+    assert_eq!(
+        "AKs, AXs".parse::<Range>(),
+        Err(RangeError::UnknownHand("AXs".to_string()))
+    );
+    assert_eq!(
+        "TTs".parse::<Range>(),
+        Err(RangeError::UnknownHand("TTs".to_string()))
+    );
+    assert_eq!(
+        "AA:2".parse::<Range>(),
+        Err(RangeError::InvalidWeight("AA:2".to_string()))
+    );
+    assert_eq!(
+        "76s-54o".parse::<Range>(),
+        Err(RangeError::InvalidSpan("76s-54o".to_string()))
+    );
+    assert_eq!(
+        "KK-AKs".parse::<Range>(),
+        Err(RangeError::InvalidSpan("KK-AKs".to_string()))
+    );
+}
+
+#[test]
+fn test_blocked_combos_are_removed() {
+    let range: Range = "AA, AK".parse().unwrap();
+    let known_cards: Vec<PokerCard> = vec!["AS".parse().unwrap(), "KD".parse().unwrap()];
+
+    // three aces make three pairs, and twelve ace-king combos are left without the ace and the king
+    assert_eq!(range.without_cards(&known_cards).len(), 3 + 9);
+}
+
+#[test]
+fn test_equity_against_a_range() {
+    let range: Range = "KK".parse().unwrap();
+    let equity = range_equity("AS AD", &range, "2C 7H 9D JC 3S", EquityMethod::Exhaustive).unwrap();
+
+    assert!(close(equity.win, 100.0));
+}
+
+#[test]
+fn test_equity_against_a_weighted_range() {
+    // the king pair beats the queens, but not the three aces left in the range
+    let range: Range = "AA:0.5, QQ".parse().unwrap();
+    let equity = range_equity("AS KS", &range, "KD 7H 9D 4C 2C", EquityMethod::Exhaustive).unwrap();
+
+    assert!(close(equity.win, 80.0));
+    assert!(close(equity.loss, 20.0));
+}
+
+#[test]
+fn test_equity_against_an_empty_range() {
+    let range: Range = "AsKs".parse().unwrap();
+
+    assert_eq!(
+        range_equity("AS AD", &range, "", EquityMethod::Exhaustive),
+        None
+    );
+}
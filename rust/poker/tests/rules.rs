@@ -0,0 +1,177 @@
+use poker::{winning_hands_with_rules, HandCombo, PokerCard, RuleSet, Value};
+
+fn cards(s: &str) -> Vec<PokerCard> {
+    s.split_whitespace()
+        .map(|card| card.parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn test_high_rules_match_winning_hands() {
+    assert_eq!(
+        winning_hands_with_rules(&["4S 5H 6C 8D KH", "2S 4H 6S 4D JH"], "", RuleSet::High),
+        vec!["2S 4H 6S 4D JH"]
+    )
+}
+
+#[test]
+fn test_high_rules_with_a_board() {
+    assert_eq!(
+        winning_hands_with_rules(&["AS KD", "9C 9D"], "9H 2S 7C KS 3D", RuleSet::High),
+        vec!["9C 9D"]
+    )
+}
+
+#[test]
+fn test_ace_to_five_wheel_is_the_best_hand() {
+    assert_eq!(
+        winning_hands_with_rules(
+            &["7S 5C 4H 3D 2C", "5H 4D 3C 2S AH", "6S 5D 4C 3H 2D"],
+            "",
+            RuleSet::AceToFiveLowball
+        ),
+        vec!["5H 4D 3C 2S AH"]
+    )
+}
+
+#[test]
+fn test_ace_to_five_ignores_flushes() {
+    assert_eq!(
+        winning_hands_with_rules(
+            &["6H 4H 3H 2H AH", "7S 4D 3C 2S AC"],
+            "",
+            RuleSet::AceToFiveLowball
+        ),
+        vec!["6H 4H 3H 2H AH"]
+    )
+}
+
+#[test]
+fn test_ace_to_five_pair_loses() {
+    assert_eq!(
+        winning_hands_with_rules(
+            &["4S 4D 3C 2S AC", "KS QD JC 9S 8C"],
+            "",
+            RuleSet::AceToFiveLowball
+        ),
+        vec!["KS QD JC 9S 8C"]
+    )
+}
+
+#[test]
+fn test_deuce_to_seven_best_hand() {
+    assert_eq!(
+        winning_hands_with_rules(
+            &["7S 5C 4H 3D 2C", "5H 4D 3C 2S AH", "6S 5D 4C 3H 2D"],
+            "",
+            RuleSet::DeuceToSevenLowball
+        ),
+        vec!["7S 5C 4H 3D 2C"]
+    )
+}
+
+#[test]
+fn test_deuce_to_seven_ace_is_high() {
+    assert_eq!(
+        winning_hands_with_rules(
+            &["5H 4D 3C 2S AH", "KS QD JC 9S 8C"],
+            "",
+            RuleSet::DeuceToSevenLowball
+        ),
+        vec!["KS QD JC 9S 8C"]
+    )
+}
+
+#[test]
+fn test_deuce_to_seven_flush_loses() {
+    assert_eq!(
+        winning_hands_with_rules(
+            &["8H 6H 4H 3H 2H", "9S 6D 4C 3S 2C"],
+            "",
+            RuleSet::DeuceToSevenLowball
+        ),
+        vec!["9S 6D 4C 3S 2C"]
+    )
+}
+
+#[test]
+fn test_omaha_uses_exactly_two_hole_cards() {
+    assert!(!matches!(
+        RuleSet::Omaha
+            .best_combos(&cards("AH 5H 6H 8H"), &cards("2H 3H 9C TD KS"))
+            .unwrap()[0],
+        HandCombo::Flush(_)
+    ));
+}
+
+#[test]
+fn test_omaha_best_hand() {
+    assert_eq!(
+        winning_hands_with_rules(
+            &["AH KH 2C 3D", "9S 9D QS JS"],
+            "QH JH TH 7S 2S",
+            RuleSet::Omaha
+        ),
+        vec!["AH KH 2C 3D"]
+    )
+}
+
+#[test]
+fn test_omaha_needs_three_board_cards() {
+    assert_eq!(
+        RuleSet::Omaha.best_combos(&cards("AH KH 2C 3D"), &cards("QH JH")),
+        None
+    )
+}
+
+#[test]
+fn test_short_deck_flush_beats_full_house() {
+    assert_eq!(
+        winning_hands_with_rules(
+            &["6H 8H 9H JH KH", "TS TD TC 7S 7D"],
+            "",
+            RuleSet::ShortDeck
+        ),
+        vec!["6H 8H 9H JH KH"]
+    )
+}
+
+#[test]
+fn test_short_deck_ace_can_start_a_straight() {
+    assert_eq!(
+        RuleSet::ShortDeck.best_combos(&cards("AS 6D 7C 8H 9S"), &[]),
+        Some(vec![HandCombo::Straight(Value::Nine)])
+    )
+}
+
+#[test]
+fn test_short_deck_rejects_low_cards() {
+    assert_eq!(
+        winning_hands_with_rules(
+            &["2S 2D 2C 7S 7D", "6S 8D 9C JS QD"],
+            "",
+            RuleSet::ShortDeck
+        ),
+        vec!["6S 8D 9C JS QD"]
+    )
+}
+
+#[test]
+fn test_hole_cards_on_the_board_are_ignored() {
+    assert_eq!(
+        winning_hands_with_rules(&["AS AH", "QC JC"], "AS KD 7C 2H 3D", RuleSet::High),
+        vec!["QC JC"]
+    );
+    assert_eq!(
+        winning_hands_with_rules(
+            &["AH KH 2C 3D", "9S 9D QS JS"],
+            "AH JH TH 7S 2S",
+            RuleSet::Omaha
+        ),
+        vec!["9S 9D QS JS"]
+    );
+    assert_eq!(
+        RuleSet::Omaha.best_combos(&cards("AH KH 2C 3D"), &cards("AH JH TH 7S 2S")),
+        None
+    );
+}
@@ -0,0 +1,264 @@
+use poker::{Street, Table, TableAction, TableError};
+
+fn table(stacks: &[u32]) -> Table {
+    let mut table = Table::new(5, 10, 42);
+    for (i, &stack) in stacks.iter().enumerate() {
+        table.seat(&format!("player {}", i), stack).unwrap();
+    }
+    table
+}
+
+fn total_chips(table: &Table) -> u32 {
+    table
+        .players()
+        .iter()
+        .map(|player| player.stack)
+        .sum::<u32>()
+        + table.pot()
+}
+
+#[test]
+fn blinds_are_posted_after_the_button() {
+    let mut table = table(&[1000, 1000, 1000]);
+    table.start_hand().unwrap();
+
+    assert_eq!(table.button(), 0);
+    assert_eq!(table.players()[1].stack, 995);
+    assert_eq!(table.players()[2].stack, 990);
+    assert_eq!(table.to_act(), Some(0));
+    assert_eq!(table.current_bet(), 10);
+    assert_eq!(table.pot(), 15);
+    assert_eq!(table.hole_cards(0).len(), 2);
+}
+
+#[test]
+fn heads_up_the_button_posts_the_small_blind() {
+    let mut table = table(&[1000, 1000]);
+    table.start_hand().unwrap();
+
+    assert_eq!(table.players()[0].stack, 995);
+    assert_eq!(table.players()[1].stack, 990);
+    assert_eq!(table.to_act(), Some(0));
+
+    table.act(0, TableAction::Call).unwrap();
+    // the big blind can still raise
+    assert_eq!(table.to_act(), Some(1));
+    table.act(1, TableAction::Check).unwrap();
+
+    assert_eq!(table.street(), Some(Street::Flop));
+    assert_eq!(table.board().len(), 3);
+    assert_eq!(table.to_act(), Some(1));
+}
+
+#[test]
+fn uncontested_pot_goes_to_the_last_player() {
+    let mut table = table(&[1000, 1000, 1000]);
+    table.start_hand().unwrap();
+
+    table.act(0, TableAction::Raise(30)).unwrap();
+    table.act(1, TableAction::Fold).unwrap();
+    table.act(2, TableAction::Fold).unwrap();
+
+    assert_eq!(table.to_act(), None);
+    assert_eq!(table.players()[0].stack, 1015);
+    assert_eq!(table.players()[1].stack, 995);
+    assert_eq!(table.players()[2].stack, 990);
+
+    let result = table.last_result().unwrap();
+    assert!(result.board.is_empty());
+    assert_eq!(result.pots[0].1, vec![0]);
+}
+
+#[test]
+fn the_button_moves_after_every_hand() {
+    let mut table = table(&[1000, 1000, 1000]);
+    table.start_hand().unwrap();
+    table.act(0, TableAction::Fold).unwrap();
+    table.act(1, TableAction::Fold).unwrap();
+
+    table.start_hand().unwrap();
+    assert_eq!(table.button(), 1);
+    assert_eq!(table.to_act(), Some(1));
+    assert_eq!(table.players()[2].stack, 1000);
+    assert_eq!(table.players()[0].stack, 990);
+}
+
+#[test]
+fn illegal_actions_are_rejected() {
+    let mut table = table(&[1000, 1000, 1000]);
+
+    assert_eq!(
+        table.act(0, TableAction::Check),
+        Err(TableError::NoHandInProgress)
+    );
+
+    table.start_hand().unwrap();
+
+    assert_eq!(table.start_hand(), Err(TableError::HandInProgress));
+    assert_eq!(
+        table.act(1, TableAction::Call),
+        Err(TableError::NotYourTurn)
+    );
+    assert_eq!(
+        table.act(0, TableAction::Check),
+        Err(TableError::CannotCheck)
+    );
+    assert_eq!(
+        table.act(0, TableAction::Raise(15)),
+        Err(TableError::RaiseTooSmall)
+    );
+    assert_eq!(
+        table.act(0, TableAction::Raise(2000)),
+        Err(TableError::NotEnoughChips)
+    );
+    assert_eq!(table.to_act(), Some(0));
+
+    table.act(0, TableAction::Call).unwrap();
+    table.act(1, TableAction::Call).unwrap();
+    assert_eq!(
+        table.act(2, TableAction::Call),
+        Err(TableError::NothingToCall)
+    );
+}
+
+#[test]
+fn a_raise_sets_the_minimum_raise() {
+    let mut table = table(&[1000, 1000, 1000]);
+    table.start_hand().unwrap();
+
+    table.act(0, TableAction::Raise(40)).unwrap();
+    assert_eq!(
+        table.act(1, TableAction::Raise(60)),
+        Err(TableError::RaiseTooSmall)
+    );
+    table.act(1, TableAction::Raise(70)).unwrap();
+    assert_eq!(table.current_bet(), 70);
+}
+
+#[test]
+fn not_enough_players_with_chips() {
+    let mut table = table(&[1000, 0]);
+
+    assert_eq!(table.start_hand(), Err(TableError::NotEnoughPlayers));
+}
+
+#[test]
+fn all_in_players_make_side_pots() {
+    let mut table = table(&[1000, 1000, 300]);
+    table.start_hand().unwrap();
+
+    table.act(0, TableAction::AllIn).unwrap();
+    table.act(1, TableAction::Call).unwrap();
+    table.act(2, TableAction::AllIn).unwrap();
+
+    // nobody can bet anymore, so the board is dealt and the hand is over
+    assert_eq!(table.to_act(), None);
+
+    let result = table.last_result().unwrap();
+    assert_eq!(result.board.len(), 5);
+    assert_eq!(result.pots.len(), 2);
+    assert_eq!(result.pots[0].0.amount, 900);
+    assert_eq!(result.pots[0].0.eligible, vec![0, 1, 2]);
+    assert_eq!(result.pots[1].0.amount, 1400);
+    assert_eq!(result.pots[1].0.eligible, vec![0, 1]);
+    assert_eq!(total_chips(&table), 2300);
+}
+
+#[test]
+fn checked_down_hand_goes_to_showdown() {
+    let mut table = table(&[1000, 1000, 1000]);
+    table.start_hand().unwrap();
+
+    table.act(0, TableAction::Call).unwrap();
+    table.act(1, TableAction::Call).unwrap();
+    table.act(2, TableAction::Check).unwrap();
+
+    for street in [Street::Flop, Street::Turn, Street::River] {
+        assert_eq!(table.street(), Some(street));
+        for seat in [1, 2, 0] {
+            assert_eq!(table.to_act(), Some(seat));
+            table.act(seat, TableAction::Check).unwrap();
+        }
+    }
+
+    assert_eq!(table.to_act(), None);
+    let result = table.last_result().unwrap();
+    assert_eq!(result.pots.len(), 1);
+    assert_eq!(result.pots[0].0.amount, 30);
+    assert!(!result.pots[0].1.is_empty());
+    assert_eq!(total_chips(&table), 3000);
+}
+
+#[test]
+fn same_seed_deals_the_same_cards() {
+    let mut first = table(&[1000, 1000, 1000]);
+    let mut second = table(&[1000, 1000, 1000]);
+    first.start_hand().unwrap();
+    second.start_hand().unwrap();
+
+    for seat in 0..3 {
+        assert_eq!(first.hole_cards(seat), second.hole_cards(seat));
+    }
+}
+
+#[test]
+fn a_short_all_in_does_not_reopen_the_betting() {
+    let mut table = table(&[1000, 1000, 45]);
+    table.start_hand().unwrap();
+
+    table.act(0, TableAction::Raise(30)).unwrap();
+    table.act(1, TableAction::Call).unwrap();
+    // 15 more than the bet is less than the minimum raise of 20
+    table.act(2, TableAction::AllIn).unwrap();
+    assert_eq!(table.current_bet(), 45);
+
+    assert_eq!(table.to_act(), Some(0));
+    assert_eq!(
+        table.act(0, TableAction::Raise(100)),
+        Err(TableError::BettingNotReopened)
+    );
+    assert_eq!(
+        table.act(0, TableAction::AllIn),
+        Err(TableError::BettingNotReopened)
+    );
+    table.act(0, TableAction::Call).unwrap();
+    table.act(1, TableAction::Call).unwrap();
+
+    assert_eq!(table.street(), Some(Street::Flop));
+    assert_eq!(table.pot(), 135);
+}
+
+#[test]
+fn a_full_all_in_reopens_the_betting() {
+    let mut table = table(&[1000, 1000, 60]);
+    table.start_hand().unwrap();
+
+    table.act(0, TableAction::Raise(30)).unwrap();
+    table.act(1, TableAction::Call).unwrap();
+    table.act(2, TableAction::AllIn).unwrap();
+
+    table.act(0, TableAction::Raise(100)).unwrap();
+    assert_eq!(table.current_bet(), 100);
+}
+
+#[test]
+fn a_full_table_refuses_more_players() {
+    let mut table = table(&[1000; Table::MAX_PLAYERS]);
+
+    assert_eq!(table.seat("one too many", 1000), Err(TableError::TableFull));
+    assert_eq!(table.players().len(), Table::MAX_PLAYERS);
+}
+
+#[test]
+fn a_full_table_deals_a_whole_hand() {
+    let mut table = table(&[1000; Table::MAX_PLAYERS]);
+    table.start_hand().unwrap();
+
+    while let Some(seat) = table.to_act() {
+        table.act(seat, TableAction::AllIn).unwrap();
+    }
+
+    let result = table.last_result().unwrap();
+    assert_eq!(result.board.len(), 5);
+    assert_eq!(total_chips(&table), 1000 * Table::MAX_PLAYERS as u32);
+}
@@ -0,0 +1,125 @@
+use poker::{
+    winning_wild_hands, CardPosition, HandCombo, PokerHandFromStrConversionError, Value, WildCard,
+    WildHand, WildRules,
+};
+
+#[test]
+fn test_joker_round_trips() {
+    let hand: WildHand = "2S JK 7H AC JK".parse().unwrap();
+
+    assert_eq!(hand.cards()[1], WildCard::Joker);
+    assert_eq!(hand.to_string(), "2S JK 7H AC JK");
+}
+
+#[test]
+fn test_hand_without_wild_cards_is_unchanged() {
+    let hand: WildHand = "4S 4H 9C 9D KS".parse().unwrap();
+
+    assert_eq!(
+        hand.combos(&WildRules::new()),
+        vec![
+            HandCombo::TwoPairs([Value::Nine, Value::Four]),
+            HandCombo::HighCard(Value::King)
+        ]
+    );
+}
+
+#[test]
+fn test_joker_pairs_the_highest_card() {
+    let hand: WildHand = "4S 8H JK 9D KS".parse().unwrap();
+
+    assert_eq!(
+        hand.combos(&WildRules::new())[0],
+        HandCombo::Pair(Value::King)
+    );
+}
+
+#[test]
+fn test_joker_completes_an_ace_to_five_straight() {
+    let hand: WildHand = "AS 2H JK 4D 5S".parse().unwrap();
+
+    assert_eq!(
+        hand.combos(&WildRules::new()),
+        vec![HandCombo::Straight(Value::Five)]
+    );
+}
+
+#[test]
+fn test_two_is_not_wild_by_default() {
+    assert_eq!(
+        winning_wild_hands(&["2S 2H 5C 9D KS", "3S 3H 5D 9C KH"], &WildRules::new()),
+        vec!["3S 3H 5D 9C KH"]
+    );
+}
+
+#[test]
+fn test_deuces_wild() {
+    assert_eq!(
+        winning_wild_hands(
+            &["2S 2H 5C 9D KS", "3S 3H 5D 9C KH"],
+            &WildRules::deuces_wild()
+        ),
+        vec!["2S 2H 5C 9D KS"]
+    );
+}
+
+#[test]
+fn test_five_of_a_kind_beats_royal_flush() {
+    assert_eq!(
+        winning_wild_hands(&["AH KH QH JH TH", "9S 9H 9C 9D JK"], &WildRules::new()),
+        vec!["9S 9H 9C 9D JK"]
+    );
+}
+
+#[test]
+fn test_custom_wild_value() {
+    let rules = WildRules::new().with_wild_value(Value::Jack);
+
+    assert_eq!(
+        winning_wild_hands(&["JS 5H 5C 8D 3S", "6S 6H 4C 4D KH"], &rules),
+        vec!["JS 5H 5C 8D 3S"]
+    );
+}
+
+#[test]
+fn test_invalid_hand_is_ignored() {
+    assert_eq!(
+        winning_wild_hands(&["JK JK", "6S 6H 4C 4D KH"], &WildRules::new()),
+        vec!["6S 6H 4C 4D KH"]
+    );
+}
+
+#[test]
+fn test_joker_completes_a_flush_with_a_missing_card() {
+    let hand: WildHand = "AS KS 9S 7S JK".parse().unwrap();
+
+    assert_eq!(
+        hand.combos(&WildRules::new()),
+        vec![HandCombo::Flush([
+            Value::Ace,
+            Value::King,
+            Value::Queen,
+            Value::Nine,
+            Value::Seven
+        ])]
+    );
+    assert_eq!(
+        winning_wild_hands(&["AS KS 9S 7S JK", "AH KH QH 9H 7H"], &WildRules::new()),
+        vec!["AS KS 9S 7S JK", "AH KH QH 9H 7H"]
+    );
+}
+
+#[test]
+fn test_duplicate_cards_in_a_wild_hand() {
+    assert_eq!(
+        "AS AS 9S 7S JK".parse::<WildHand>(),
+        Err(PokerHandFromStrConversionError::DuplicateCard(
+            CardPosition {
+                index: 1,
+                offset: 3,
+                text: "AS".to_string()
+            }
+        ))
+    );
+    assert!("JK AS JK 7S JK".parse::<WildHand>().is_ok());
+}
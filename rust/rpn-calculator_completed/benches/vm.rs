@@ -0,0 +1,44 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rpn_calculator::{compile, evaluate, tokenize, CalculatorInput};
+
+// a polynomial of x and y, with a local variable
+const FORMULA: &str = "@x dup * !square @square @x * 3 * @square @y * 2 * + @y 7 * - 5 +";
+
+fn vm(c: &mut Criterion) {
+    let formula: Vec<CalculatorInput<i64>> = tokenize(FORMULA).unwrap();
+    let program = compile(&formula).unwrap();
+    let parameters: Vec<(i64, i64)> = (0..100).map(|i| (i - 50, 2 * i + 1)).collect();
+
+    let mut group = c.benchmark_group("formula with parameters");
+
+    group.bench_function("evaluate", |b| {
+        b.iter(|| {
+            for &(x, y) in &parameters
+            {
+                // the parameters are stored before the formula is walked
+                let mut inputs = vec![
+                    CalculatorInput::Value(x),
+                    CalculatorInput::Store("x".to_string()),
+                    CalculatorInput::Value(y),
+                    CalculatorInput::Store("y".to_string()),
+                ];
+                inputs.extend(formula.iter().cloned());
+                black_box(evaluate(black_box(&inputs)).unwrap());
+            }
+        })
+    });
+
+    group.bench_function("compiled", |b| {
+        b.iter(|| {
+            for &(x, y) in &parameters
+            {
+                black_box(program.run(black_box(&[x, y])).unwrap());
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, vm);
+criterion_main!(benches);
@@ -0,0 +1,160 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::process::ExitCode;
+
+use rpn_calculator::{tokenize, Calculator, Number, Rational};
+
+const USAGE: &str = "usage: rpn [--numbers i32|i64|f64|rational] [FILE]
+
+Runs RPN expressions like `3 4 + 2 *` line by line, on a stack kept from one line to the next,
+and prints the stack after each line. The expressions are read from FILE, or from the standard
+input without one: interactively when it is a terminal, and stopping at the first error otherwise.
+
+Commands:
+    undo      cancels the last line
+    history   lists the lines run so far
+    quit      exits";
+
+// the numbers the calculator can work with
+const NUMBERS: [&str; 4] = ["i32", "i64", "f64", "rational"];
+
+fn main() -> ExitCode {
+    let mut numbers = "i32".to_string();
+    let mut path: Option<String> = None;
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next()
+    {
+        match arg.as_str()
+        {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            "--numbers" => match args.next()
+            {
+                Some(value) if NUMBERS.contains(&value.as_str()) => numbers = value,
+                _ => return usage_error(),
+            },
+            _ if path.is_none() && !arg.starts_with('-') => path = Some(arg),
+            _ => return usage_error(),
+        }
+    }
+
+    let result = match path
+    {
+        Some(path) => match File::open(&path)
+        {
+            Ok(file) => run(&numbers, BufReader::new(file), false),
+            Err(error) => {
+                eprintln!("rpn: cannot open {}: {}", path, error);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => {
+            let stdin = io::stdin();
+            let interactive = stdin.is_terminal();
+            run(&numbers, stdin.lock(), interactive)
+        }
+    };
+
+    match result
+    {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("rpn: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage_error() -> ExitCode {
+    eprintln!("{}", USAGE);
+    ExitCode::from(2)
+}
+
+fn run(numbers: &str, input: impl BufRead, interactive: bool) -> io::Result<bool> {
+    let mut output = io::stdout().lock();
+
+    match numbers
+    {
+        "i64" => repl::<i64>(input, &mut output, interactive),
+        "f64" => repl::<f64>(input, &mut output, interactive),
+        "rational" => repl::<Rational>(input, &mut output, interactive),
+        _ => repl::<i32>(input, &mut output, interactive),
+    }
+}
+
+// runs every line, returning whether they all succeeded
+fn repl<N: Number>(input: impl BufRead, output: &mut impl Write, interactive: bool) -> io::Result<bool> {
+    let mut calculator: Calculator<N> = Calculator::new();
+    // the calculator before each line which was run, for undo
+    let mut previous: Vec<Calculator<N>> = vec!();
+    let mut history: Vec<String> = vec!();
+    let mut lines = input.lines().enumerate();
+
+    loop
+    {
+        if interactive
+        {
+            write!(output, "> ")?;
+            output.flush()?;
+        }
+
+        let (line_number, line) = match lines.next()
+        {
+            Some((line_number, line)) => (line_number + 1, line?),
+            None => break,
+        };
+
+        match line.trim()
+        {
+            "" => continue,
+            "quit" => break,
+            "undo" => match previous.pop()
+            {
+                Some(before) => calculator = before,
+                None => writeln!(output, "nothing to undo")?,
+            },
+            "history" => {
+                for (i, line) in history.iter().enumerate()
+                {
+                    writeln!(output, "{:4}  {}", i + 1, line)?;
+                }
+                continue;
+            }
+            line => {
+                history.push(line.to_string());
+
+                // a line which fails has no effect at all
+                let before = calculator.clone();
+                let error = match tokenize::<N>(line)
+                {
+                    Some(inputs) => calculator.run(&inputs).err().map(|error| error.to_string()),
+                    None => Some("cannot parse the line".to_string()),
+                };
+
+                match error
+                {
+                    Some(error) => {
+                        calculator = before;
+                        if !interactive
+                        {
+                            eprintln!("rpn: line {}: {}", line_number, error);
+                            return Ok(false);
+                        }
+                        writeln!(output, "error: {}", error)?;
+                    }
+                    None => previous.push(before),
+                }
+            }
+        }
+
+        let stack: Vec<String> = calculator.stack().iter().map(N::to_string).collect();
+        writeln!(output, "{}", stack.join(" "))?;
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::{CalculatorInput, ErrorKind, Number, Step};
+
+/// The words and the variables defined while evaluating inputs.
+#[derive(Clone, Debug)]
+pub(crate) struct Dictionary<N> {
+    // the bodies of the words, in which the user-defined words were already resolved to their
+    // bodies, so that redefining a word does not change the words which use it
+    words: HashMap<String, Rc<Vec<Op<N>>>>,
+    variables: HashMap<String, N>,
+    // the indices of the loops being run, the innermost one last
+    loops: Vec<N>,
+}
+
+// an input as it is run: the inputs of a body are the ones it was defined with, except for the
+// words, which are resolved to the bodies they had, and shared rather than copied so that words
+// defined from other words do not grow with every definition
+#[derive(Clone, Debug)]
+enum Op<N> {
+    // an input which runs no other inputs, or a word looked up when it is run
+    Input(CalculatorInput<N>),
+    Resolved(Rc<Vec<Op<N>>>),
+    If(Vec<Op<N>>, Vec<Op<N>>),
+    DoLoop(Vec<Op<N>>),
+    BeginUntil(Vec<Op<N>>),
+}
+
+/// What a run may still do, and who watches it.
+pub(crate) struct Context<'a, N> {
+    // how many more inputs may be run
+    steps: usize,
+    // the index of the input given to the calculator which is being run
+    pub(crate) index: usize,
+    // how many words and control structures the input being run is in
+    depth: usize,
+    // sees every input run but the words and control structures, and stops the run when it
+    // returns false
+    observer: Option<&'a mut dyn FnMut(Step<N>) -> bool>,
+}
+
+impl<'a, N> Context<'a, N> {
+    pub(crate) fn new(steps: usize, observer: Option<&'a mut dyn FnMut(Step<N>) -> bool>) -> Self {
+        Context {
+            steps,
+            index: 0,
+            depth: 0,
+            observer,
+        }
+    }
+}
+
+impl<N: Number> Dictionary<N> {
+    pub(crate) fn new() -> Self {
+        Dictionary {
+            words: HashMap::new(),
+            variables: HashMap::new(),
+            loops: vec!(),
+        }
+    }
+
+    /// Runs a single input, counting it and every input it runs in turn as steps of the run.
+    /// When it fails, the stack is left as it was before.
+    pub(crate) fn run(&mut self, input: &CalculatorInput<N>, stack: &mut Vec<N>, context: &mut Context<'_, N>) -> Result<(), ErrorKind> {
+        // the words given to the calculator are looked up when they are run
+        let op = self.op(input, false)?;
+        self.run_op(&op, stack, context)
+    }
+
+    fn run_op(&mut self, op: &Op<N>, stack: &mut Vec<N>, context: &mut Context<'_, N>) -> Result<(), ErrorKind> {
+        if context.steps == 0
+        {
+            return Err(ErrorKind::StepLimitExceeded);
+        }
+        context.steps -= 1;
+
+        match op
+        {
+            Op::Input(CalculatorInput::Word(_))
+            | Op::Resolved(_)
+            | Op::If(..)
+            | Op::DoLoop(_)
+            | Op::BeginUntil(_) => {
+                let before = stack.clone();
+                context.depth += 1;
+                let result = self.run_control(op, stack, context);
+                context.depth -= 1;
+
+                if let Err(kind) = result
+                {
+                    *stack = before;
+                    return Err(kind);
+                }
+                Ok(())
+            }
+            Op::Input(input) => match context.observer.as_mut()
+            {
+                None => self.run_one(input, stack),
+                Some(observer) => {
+                    let before = stack.clone();
+                    let result = self.run_one(input, stack);
+                    let step = Step {
+                        index: context.index,
+                        depth: context.depth,
+                        input: input.clone(),
+                        before: before.clone(),
+                        after: result.map(|_| stack.clone()),
+                    };
+
+                    if !observer(step)
+                    {
+                        *stack = before;
+                        return Err(ErrorKind::Interrupted);
+                    }
+                    result
+                }
+            },
+        }
+    }
+
+    // runs an input which does not run other inputs
+    fn run_one(&mut self, input: &CalculatorInput<N>, stack: &mut Vec<N>) -> Result<(), ErrorKind> {
+        match input
+        {
+            CalculatorInput::Define(name, body) => self.define(name, body)?,
+            CalculatorInput::LoopIndex => {
+                stack.push(self.loops.last().ok_or(ErrorKind::NoLoop)?.clone());
+            }
+            CalculatorInput::Store(name) => {
+                let value = stack.pop().ok_or(ErrorKind::StackUnderflow)?;
+                self.variables.insert(name.clone(), value);
+            }
+            CalculatorInput::Fetch(name) => {
+                stack.push(self.variables.get(name).ok_or(ErrorKind::UnknownVariable)?.clone());
+            }
+            _ => {
+                if stack.len() < input.arity()
+                {
+                    return Err(ErrorKind::StackUnderflow);
+                }
+
+                let operands = stack.split_off(stack.len() - input.arity());
+                match input.apply(&operands)
+                {
+                    Ok(results) => stack.extend(results),
+                    Err(kind) => {
+                        stack.extend(operands);
+                        return Err(kind);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn define(&mut self, name: &str, body: &[CalculatorInput<N>]) -> Result<(), ErrorKind> {
+        let body = self.ops(body, true)?;
+        self.words.insert(name.to_string(), Rc::new(body));
+        Ok(())
+    }
+
+    /// The inputs a word runs, the words it uses being replaced with the inputs they run.
+    pub(crate) fn word(&self, name: &str) -> Result<Vec<CalculatorInput<N>>, ErrorKind> {
+        Ok(expand(&self.body(name)?))
+    }
+
+    fn body(&self, name: &str) -> Result<Rc<Vec<Op<N>>>, ErrorKind> {
+        self.words.get(name).cloned().ok_or(ErrorKind::UnknownWord)
+    }
+
+    // runs the inputs which run other inputs, which may leave the stack changed when they fail
+    fn run_control(&mut self, op: &Op<N>, stack: &mut Vec<N>, context: &mut Context<'_, N>) -> Result<(), ErrorKind> {
+        match op
+        {
+            Op::Input(CalculatorInput::Word(name)) => {
+                let body = self.body(name)?;
+                self.run_all(&body, stack, context)
+            }
+            Op::Resolved(body) => self.run_all(body, stack, context),
+            Op::If(then, otherwise) => {
+                let flag = stack.pop().ok_or(ErrorKind::StackUnderflow)?;
+                match flag.is_true()
+                {
+                    true => self.run_all(then, stack, context),
+                    false => self.run_all(otherwise, stack, context),
+                }
+            }
+            Op::DoLoop(body) => {
+                if stack.len() < 2
+                {
+                    return Err(ErrorKind::StackUnderflow);
+                }
+                let mut index = stack.pop().unwrap();
+                let limit = stack.pop().unwrap();
+
+                while index < limit
+                {
+                    self.loops.push(index);
+                    let result = self.run_all(body, stack, context);
+                    index = self.loops.pop().unwrap();
+                    result?;
+
+                    index = index.try_add(&N::from_bool(true))?;
+                }
+                Ok(())
+            }
+            Op::BeginUntil(body) => {
+                loop
+                {
+                    self.run_all(body, stack, context)?;
+                    if stack.pop().ok_or(ErrorKind::StackUnderflow)?.is_true()
+                    {
+                        return Ok(());
+                    }
+                }
+            }
+            Op::Input(_) => unreachable!(), // `run_op` only gives the inputs which run other inputs
+        }
+    }
+
+    fn run_all(&mut self, ops: &[Op<N>], stack: &mut Vec<N>, context: &mut Context<'_, N>) -> Result<(), ErrorKind> {
+        for op in ops
+        {
+            self.run_op(op, stack, context)?;
+        }
+
+        Ok(())
+    }
+
+    // the op of an input, in which the user-defined words are resolved to their current bodies
+    // when `resolve` is true, and are looked up when they are run otherwise
+    fn op(&self, input: &CalculatorInput<N>, resolve: bool) -> Result<Op<N>, ErrorKind> {
+        let op = match input
+        {
+            CalculatorInput::Word(name) if resolve => Op::Resolved(self.body(name)?),
+            CalculatorInput::If(then, otherwise) => Op::If(self.ops(then, resolve)?, self.ops(otherwise, resolve)?),
+            CalculatorInput::DoLoop(body) => Op::DoLoop(self.ops(body, resolve)?),
+            CalculatorInput::BeginUntil(body) => Op::BeginUntil(self.ops(body, resolve)?),
+            input => Op::Input(input.clone()),
+        };
+
+        Ok(op)
+    }
+
+    fn ops(&self, inputs: &[CalculatorInput<N>], resolve: bool) -> Result<Vec<Op<N>>, ErrorKind> {
+        inputs.iter().map(|input| self.op(input, resolve)).collect()
+    }
+}
+
+// turns ops back into the inputs they run
+fn expand<N: Clone>(ops: &[Op<N>]) -> Vec<CalculatorInput<N>> {
+    let mut inputs = vec!();
+
+    for op in ops
+    {
+        match op
+        {
+            Op::Input(input) => inputs.push(input.clone()),
+            Op::Resolved(body) => inputs.extend(expand(body)),
+            Op::If(then, otherwise) => inputs.push(CalculatorInput::If(expand(then), expand(otherwise))),
+            Op::DoLoop(body) => inputs.push(CalculatorInput::DoLoop(expand(body))),
+            Op::BeginUntil(body) => inputs.push(CalculatorInput::BeginUntil(expand(body))),
+        }
+    }
+
+    inputs
+}
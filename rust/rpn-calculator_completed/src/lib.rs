@@ -1,44 +1,275 @@
-#[derive(Debug)]
-pub enum CalculatorInput {
+use std::error::Error;
+use std::fmt;
+
+mod dictionary;
+mod number;
+mod parse;
+mod symbolic;
+mod trace;
+mod vm;
+
+use dictionary::{Context, Dictionary};
+
+pub use number::{Number, Rational};
+pub use parse::{evaluate_infix, infix_to_rpn, tokenize};
+pub use symbolic::{symbolic, BinaryOperator, Expression};
+pub use trace::{debug, trace, Command, Step, Trace};
+pub use vm::{compile, CompileError, Instruction, Program};
+
+/// An input of the calculator: a value to push on the stack, or a word working on the values
+/// at the top of the stack.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CalculatorInput<N = i32> {
     Add,
     Subtract,
     Multiply,
     Divide,
-    Value(i32),
+    Modulo,
+    Power,
+    Negate,
+    Abs,
+    Min,
+    Max,
+    /// `a b -> a = b`, which is `1` when true and `0` when false, like the other comparisons.
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    /// `a -> a a`
+    Dup,
+    /// `a ->`
+    Drop,
+    /// `a b -> b a`
+    Swap,
+    /// `a b -> a b a`
+    Over,
+    /// `a b c -> b c a`
+    Rot,
+    Value(N),
+    /// Defines a word, which runs its body when it is used.
+    ///
+    /// The words used in the body are the ones defined at this point: redefining them later
+    /// does not change the new word.
+    Define(String, Vec<CalculatorInput<N>>),
+    /// Runs a word defined earlier.
+    Word(String),
+    /// Takes the value at the top of the stack and stores it in a variable.
+    Store(String),
+    /// Pushes the value of a variable.
+    Fetch(String),
+    /// Takes a flag from the stack, and runs the first inputs when it is true and the second ones
+    /// otherwise.
+    If(Vec<CalculatorInput<N>>, Vec<CalculatorInput<N>>),
+    /// Takes a start and, below it, a limit from the stack, and runs the inputs once for each
+    /// index from the start up to the limit, excluded.
+    DoLoop(Vec<CalculatorInput<N>>),
+    /// Pushes the index of the innermost loop.
+    LoopIndex,
+    /// Runs the inputs, then takes a flag from the stack, until the flag is true.
+    BeginUntil(Vec<CalculatorInput<N>>),
 }
 
-pub fn evaluate(inputs: &[CalculatorInput]) -> Option<i32> {
-    let mut stack: Vec<i32> = vec!();
+/// Why an evaluation failed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// An input was found with less operands on the stack than it needs.
+    StackUnderflow,
+    DivisionByZero,
+    /// The result of an operator does not fit in the numeric type.
+    Overflow,
+    /// The exponent is negative, too big, or not an integer where one is needed.
+    InvalidExponent,
+    UnknownWord,
+    /// A variable was fetched before anything was stored in it.
+    UnknownVariable,
+    /// Every input was evaluated, but the stack does not hold exactly one result.
+    NoSingleResult,
+    /// The loop index was asked for outside of a loop.
+    NoLoop,
+    /// The branches of an `If` leave different amounts of values on the stack, or a loop does
+    /// not leave the same amount of values every time, so that the program cannot be compiled.
+    UnbalancedStack,
+    /// The input has no formula, like the comparisons and the control structures.
+    NotSymbolic,
+    /// More inputs were evaluated than the step limit allows.
+    StepLimitExceeded,
+    /// The debugger stopped the evaluation.
+    Interrupted,
+    /// The program has more instructions, constants or variables than the bytecode can refer to.
+    ProgramTooLarge,
+}
 
-    for input in inputs
-    {
-        match input
+/// An evaluation failure: the index of the input which failed (the amount of inputs when the
+/// evaluation failed at the end), what went wrong, and the stack before the input was evaluated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalculatorError<N = i32> {
+    pub index: usize,
+    pub kind: ErrorKind,
+    pub stack: Vec<N>,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self
         {
-            CalculatorInput::Value(value) => stack.push(value.to_owned()),
-            _ => {
-                if stack.len() < 2
-                {
-                    return None;
-                }
-                else
-                {
-                    let operands = (stack.pop().unwrap(), stack.pop().unwrap());
-                    match input
-                    {
-                        CalculatorInput::Add => stack.push(operands.1 + operands.0),
-                        CalculatorInput::Subtract => stack.push(operands.1 - operands.0),
-                        CalculatorInput::Divide => stack.push(operands.1 / operands.0),
-                        CalculatorInput::Multiply => stack.push(operands.1 * operands.0),
-                        _ => {}
-                    }
-                }
-            }
+            ErrorKind::StackUnderflow => "not enough operands",
+            ErrorKind::DivisionByZero => "division by zero",
+            ErrorKind::Overflow => "arithmetic overflow",
+            ErrorKind::InvalidExponent => "invalid exponent",
+            ErrorKind::UnknownWord => "unknown word",
+            ErrorKind::UnknownVariable => "unknown variable",
+            ErrorKind::NoSingleResult => "the stack must end with exactly one value",
+            ErrorKind::NoLoop => "loop index outside of a loop",
+            ErrorKind::UnbalancedStack => "unbalanced branches or loop",
+            ErrorKind::NotSymbolic => "no formula for this input",
+            ErrorKind::StepLimitExceeded => "step limit exceeded",
+            ErrorKind::Interrupted => "interrupted",
+            ErrorKind::ProgramTooLarge => "program too large",
+        };
+
+        write!(f, "{}", message)
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for CalculatorError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stack: Vec<String> = self.stack.iter().map(N::to_string).collect();
+        write!(f, "{} at input {} (stack: [{}])", self.kind, self.index, stack.join(", "))
+    }
+}
+
+impl<N: fmt::Debug + fmt::Display> Error for CalculatorError<N> {}
+
+impl<N: Number> CalculatorInput<N> {
+    // how many values a built-in input takes from the top of the stack
+    fn arity(&self) -> usize {
+        match self
+        {
+            CalculatorInput::Value(_) => 0,
+            CalculatorInput::Negate | CalculatorInput::Abs | CalculatorInput::Dup | CalculatorInput::Drop => 1,
+            CalculatorInput::Rot => 3,
+            _ => 2,
+        }
+    }
+
+    // the values a built-in input puts back on the stack in place of its operands
+    fn apply(&self, operands: &[N]) -> Result<Vec<N>, ErrorKind> {
+        let result = match (self, operands)
+        {
+            (CalculatorInput::Value(value), []) => value.clone(),
+            (CalculatorInput::Add, [a, b]) => a.try_add(b)?,
+            (CalculatorInput::Subtract, [a, b]) => a.try_sub(b)?,
+            (CalculatorInput::Multiply, [a, b]) => a.try_mul(b)?,
+            (CalculatorInput::Divide, [a, b]) => a.try_div(b)?,
+            (CalculatorInput::Modulo, [a, b]) => a.try_rem(b)?,
+            (CalculatorInput::Power, [a, b]) => a.try_pow(b)?,
+            (CalculatorInput::Negate, [a]) => a.try_neg()?,
+            (CalculatorInput::Abs, [a]) => a.try_abs()?,
+            (CalculatorInput::Min, [a, b]) => if b < a { b.clone() } else { a.clone() },
+            (CalculatorInput::Max, [a, b]) => if b > a { b.clone() } else { a.clone() },
+            (CalculatorInput::Equal, [a, b]) => N::from_bool(a == b),
+            (CalculatorInput::NotEqual, [a, b]) => N::from_bool(a != b),
+            (CalculatorInput::Less, [a, b]) => N::from_bool(a < b),
+            (CalculatorInput::LessEqual, [a, b]) => N::from_bool(a <= b),
+            (CalculatorInput::Greater, [a, b]) => N::from_bool(a > b),
+            (CalculatorInput::GreaterEqual, [a, b]) => N::from_bool(a >= b),
+            (CalculatorInput::Dup, [a]) => return Ok(vec!(a.clone(), a.clone())),
+            (CalculatorInput::Drop, [_]) => return Ok(vec!()),
+            (CalculatorInput::Swap, [a, b]) => return Ok(vec!(b.clone(), a.clone())),
+            (CalculatorInput::Over, [a, b]) => return Ok(vec!(a.clone(), b.clone(), a.clone())),
+            (CalculatorInput::Rot, [a, b, c]) => return Ok(vec!(b.clone(), c.clone(), a.clone())),
+            _ => unreachable!(), // the amount of operands always matches the arity, and the dictionary runs the other inputs
         };
+
+        Ok(vec!(result))
     }
+}
+
+/// The amount of inputs a run evaluates before giving up, unless the calculator was given
+/// another step limit.
+pub const DEFAULT_STEP_LIMIT: usize = 1_000_000;
+
+/// A calculator which keeps its stack, words and variables from one run to the next.
+#[derive(Clone, Debug)]
+pub struct Calculator<N = i32> {
+    stack: Vec<N>,
+    dictionary: Dictionary<N>,
+    step_limit: usize,
+}
+
+impl<N: Number> Calculator<N> {
+    pub fn new() -> Self {
+        Calculator {
+            stack: vec!(),
+            dictionary: Dictionary::new(),
+            step_limit: DEFAULT_STEP_LIMIT,
+        }
+    }
+
+    pub fn stack(&self) -> &[N] {
+        &self.stack
+    }
+
+    /// Sets the amount of inputs a run may evaluate, counting every input run by the words and
+    /// by each iteration of the loops, so that a runaway program fails instead of hanging.
+    pub fn set_step_limit(&mut self, step_limit: usize) {
+        self.step_limit = step_limit;
+    }
+
+    /// Runs the inputs one after the other, on top of the current stack.
+    ///
+    /// When an input fails, the inputs before it keep their effects and the error holds
+    /// the stack before the failing input, which is also left on the calculator.
+    pub fn run(&mut self, inputs: &[CalculatorInput<N>]) -> Result<(), CalculatorError<N>> {
+        self.run_in(inputs, &mut Context::new(self.step_limit, None))
+    }
+
+    fn run_in(&mut self, inputs: &[CalculatorInput<N>], context: &mut Context<'_, N>) -> Result<(), CalculatorError<N>> {
+        for (index, input) in inputs.iter().enumerate()
+        {
+            context.index = index;
+            if let Err(kind) = self.dictionary.run(input, &mut self.stack, context)
+            {
+                return Err(CalculatorError {
+                    index,
+                    kind,
+                    stack: self.stack.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<N: Number> Default for Calculator<N> {
+    fn default() -> Self {
+        Calculator::new()
+    }
+}
+
+/// Evaluates RPN inputs, with any type of number.
+///
+/// The words and variables defined by the inputs only last for this evaluation, which may run
+/// up to `DEFAULT_STEP_LIMIT` inputs.
+pub fn evaluate<N: Number>(inputs: &[CalculatorInput<N>]) -> Result<N, CalculatorError<N>> {
+    evaluate_in(inputs, &mut Context::new(DEFAULT_STEP_LIMIT, None))
+}
+
+fn evaluate_in<N: Number>(inputs: &[CalculatorInput<N>], context: &mut Context<'_, N>) -> Result<N, CalculatorError<N>> {
+    let mut calculator: Calculator<N> = Calculator::new();
+    calculator.run_in(inputs, context)?;
 
+    let mut stack = calculator.stack;
     match stack.len()
     {
-        1 => Some(stack[0].to_owned()),
-        _ => None
+        1 => Ok(stack.remove(0)),
+        _ => Err(CalculatorError {
+            index: inputs.len(),
+            kind: ErrorKind::NoSingleResult,
+            stack,
+        })
     }
 }
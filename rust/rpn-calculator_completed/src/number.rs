@@ -0,0 +1,222 @@
+use std::fmt;
+use std::str::FromStr;
+
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::ErrorKind;
+
+/// Arbitrary-precision rationals, written like `3` or `-1/2`.
+pub type Rational = BigRational;
+
+/// The numbers the calculator can work with: `i32`, `i64`, `f64` and `Rational`.
+///
+/// Every operation fails instead of panicking or silently wrapping around.
+/// The booleans are `1` for true and `0` for false, and every value but `0` is true.
+pub trait Number: Clone + PartialEq + PartialOrd + fmt::Debug + fmt::Display + FromStr {
+    fn from_bool(value: bool) -> Self;
+    fn is_true(&self) -> bool;
+    fn try_add(&self, other: &Self) -> Result<Self, ErrorKind>;
+    fn try_sub(&self, other: &Self) -> Result<Self, ErrorKind>;
+    fn try_mul(&self, other: &Self) -> Result<Self, ErrorKind>;
+    fn try_div(&self, other: &Self) -> Result<Self, ErrorKind>;
+    fn try_rem(&self, other: &Self) -> Result<Self, ErrorKind>;
+    fn try_pow(&self, exponent: &Self) -> Result<Self, ErrorKind>;
+    fn try_neg(&self) -> Result<Self, ErrorKind>;
+    fn try_abs(&self) -> Result<Self, ErrorKind>;
+}
+
+macro_rules! impl_integer_number {
+    ($($integer:ty),*) => {
+        $(
+            impl Number for $integer {
+                fn from_bool(value: bool) -> Self {
+                    value as $integer
+                }
+
+                fn is_true(&self) -> bool {
+                    *self != 0
+                }
+
+                fn try_add(&self, other: &Self) -> Result<Self, ErrorKind> {
+                    self.checked_add(*other).ok_or(ErrorKind::Overflow)
+                }
+
+                fn try_sub(&self, other: &Self) -> Result<Self, ErrorKind> {
+                    self.checked_sub(*other).ok_or(ErrorKind::Overflow)
+                }
+
+                fn try_mul(&self, other: &Self) -> Result<Self, ErrorKind> {
+                    self.checked_mul(*other).ok_or(ErrorKind::Overflow)
+                }
+
+                fn try_div(&self, other: &Self) -> Result<Self, ErrorKind> {
+                    if *other == 0
+                    {
+                        return Err(ErrorKind::DivisionByZero);
+                    }
+                    self.checked_div(*other).ok_or(ErrorKind::Overflow)
+                }
+
+                fn try_rem(&self, other: &Self) -> Result<Self, ErrorKind> {
+                    if *other == 0
+                    {
+                        return Err(ErrorKind::DivisionByZero);
+                    }
+                    self.checked_rem(*other).ok_or(ErrorKind::Overflow)
+                }
+
+                fn try_pow(&self, exponent: &Self) -> Result<Self, ErrorKind> {
+                    let exponent = u32::try_from(*exponent).map_err(|_| ErrorKind::InvalidExponent)?;
+                    self.checked_pow(exponent).ok_or(ErrorKind::Overflow)
+                }
+
+                fn try_neg(&self) -> Result<Self, ErrorKind> {
+                    self.checked_neg().ok_or(ErrorKind::Overflow)
+                }
+
+                fn try_abs(&self) -> Result<Self, ErrorKind> {
+                    self.checked_abs().ok_or(ErrorKind::Overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_number!(i32, i64);
+
+// the bits a rational power may have, about 300 000 digits
+const MAX_POWER_BITS: u64 = 1 << 20;
+
+// infinities only come from operations which overflowed, and NaN from operations on them
+fn finite(value: f64) -> Result<f64, ErrorKind> {
+    match value.is_finite()
+    {
+        true => Ok(value),
+        false => Err(ErrorKind::Overflow),
+    }
+}
+
+impl Number for f64 {
+    fn from_bool(value: bool) -> Self {
+        if value { 1.0 } else { 0.0 }
+    }
+
+    fn is_true(&self) -> bool {
+        *self != 0.0
+    }
+
+    fn try_add(&self, other: &Self) -> Result<Self, ErrorKind> {
+        finite(self + other)
+    }
+
+    fn try_sub(&self, other: &Self) -> Result<Self, ErrorKind> {
+        finite(self - other)
+    }
+
+    fn try_mul(&self, other: &Self) -> Result<Self, ErrorKind> {
+        finite(self * other)
+    }
+
+    fn try_div(&self, other: &Self) -> Result<Self, ErrorKind> {
+        if *other == 0.0
+        {
+            return Err(ErrorKind::DivisionByZero);
+        }
+        finite(self / other)
+    }
+
+    fn try_rem(&self, other: &Self) -> Result<Self, ErrorKind> {
+        if *other == 0.0
+        {
+            return Err(ErrorKind::DivisionByZero);
+        }
+        finite(self % other)
+    }
+
+    fn try_pow(&self, exponent: &Self) -> Result<Self, ErrorKind> {
+        let value = self.powf(*exponent);
+        // like the root of a negative number
+        if value.is_nan() && self.is_finite() && exponent.is_finite()
+        {
+            return Err(ErrorKind::InvalidExponent);
+        }
+        finite(value)
+    }
+
+    fn try_neg(&self) -> Result<Self, ErrorKind> {
+        Ok(-self)
+    }
+
+    fn try_abs(&self) -> Result<Self, ErrorKind> {
+        Ok(self.abs())
+    }
+}
+
+impl Number for Rational {
+    fn from_bool(value: bool) -> Self {
+        if value { Rational::one() } else { Rational::zero() }
+    }
+
+    fn is_true(&self) -> bool {
+        !self.is_zero()
+    }
+
+    fn try_add(&self, other: &Self) -> Result<Self, ErrorKind> {
+        Ok(self + other)
+    }
+
+    fn try_sub(&self, other: &Self) -> Result<Self, ErrorKind> {
+        Ok(self - other)
+    }
+
+    fn try_mul(&self, other: &Self) -> Result<Self, ErrorKind> {
+        Ok(self * other)
+    }
+
+    fn try_div(&self, other: &Self) -> Result<Self, ErrorKind> {
+        if other.is_zero()
+        {
+            return Err(ErrorKind::DivisionByZero);
+        }
+        Ok(self / other)
+    }
+
+    fn try_rem(&self, other: &Self) -> Result<Self, ErrorKind> {
+        if other.is_zero()
+        {
+            return Err(ErrorKind::DivisionByZero);
+        }
+        Ok(self % other)
+    }
+
+    /// The exponent must be an integer which fits in an `i32`, and the result overflows past
+    /// about a million bits.
+    fn try_pow(&self, exponent: &Self) -> Result<Self, ErrorKind> {
+        if !exponent.is_integer()
+        {
+            return Err(ErrorKind::InvalidExponent);
+        }
+        let exponent = exponent.to_integer().to_i32().ok_or(ErrorKind::InvalidExponent)?;
+
+        if exponent < 0 && self.is_zero()
+        {
+            return Err(ErrorKind::DivisionByZero);
+        }
+        // the powers of 0, 1 and -1 never grow, and the others have at least this many bits
+        let bits = self.numer().bits().max(self.denom().bits()) - 1;
+        if bits.saturating_mul(exponent.unsigned_abs() as u64) > MAX_POWER_BITS
+        {
+            return Err(ErrorKind::Overflow);
+        }
+        Ok(self.pow(exponent))
+    }
+
+    fn try_neg(&self) -> Result<Self, ErrorKind> {
+        Ok(-self)
+    }
+
+    fn try_abs(&self) -> Result<Self, ErrorKind> {
+        Ok(Signed::abs(self))
+    }
+}
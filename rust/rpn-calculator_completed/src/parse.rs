@@ -0,0 +1,337 @@
+use std::fmt;
+use std::str::SplitWhitespace;
+
+use crate::{CalculatorInput, Number};
+
+/// Parses space separated RPN tokens, like `"3 4 + 2 *"`.
+///
+/// The operators are `+ - * / % ^` and the comparisons `= <> < <= > >=`, followed by the words
+/// `neg abs min max dup drop swap over rot`.
+/// `: square dup * ;` defines the word `square`, `!x` stores the top of the stack in the
+/// variable `x` and `@x` pushes its value. Word and variable names start with a letter and are
+/// made of letters, digits, `-` and `_`.
+/// `if ... else ... then` (where `else ...` is optional), `limit start do ... loop` (where `i`
+/// is the loop index) and `begin ... until` can be nested, also in definitions.
+/// Returns `None` if a token is neither a number, an operator nor a name, or if a definition
+/// or a control structure is not well formed.
+pub fn tokenize<N: Number>(s: &str) -> Option<Vec<CalculatorInput<N>>> {
+    match block(&mut s.split_whitespace(), &[], true)?
+    {
+        (inputs, None) => Some(inputs),
+        _ => None,
+    }
+}
+
+// the words which delimit definitions and control structures
+const KEYWORDS: [&str; 9] = [":", ";", "if", "else", "then", "do", "loop", "begin", "until"];
+
+// parses the inputs up to one of the `ends`, which is returned with them, or up to the last
+// token when there are no `ends`
+fn block<'a, N: Number>(
+    tokens: &mut SplitWhitespace<'a>,
+    ends: &[&str],
+    definitions: bool,
+) -> Option<(Vec<CalculatorInput<N>>, Option<&'a str>)> {
+    let mut inputs: Vec<CalculatorInput<N>> = vec!();
+
+    loop
+    {
+        let token = match tokens.next()
+        {
+            Some(token) => token,
+            None if ends.is_empty() => return Some((inputs, None)),
+            None => return None,
+        };
+        if ends.contains(&token)
+        {
+            return Some((inputs, Some(token)));
+        }
+
+        let input = match token
+        {
+            ":" if definitions => {
+                // the built-in words cannot be redefined
+                let name = tokens.next().filter(|name| {
+                    is_name(name) && built_in::<N>(name).is_none() && !KEYWORDS.contains(name)
+                })?;
+                let (body, _) = block(tokens, &[";"], false)?;
+                CalculatorInput::Define(name.to_string(), body)
+            }
+            "if" => {
+                let (then, end) = block(tokens, &["else", "then"], false)?;
+                let otherwise = match end
+                {
+                    Some("else") => block(tokens, &["then"], false)?.0,
+                    _ => vec!(),
+                };
+                CalculatorInput::If(then, otherwise)
+            }
+            "do" => CalculatorInput::DoLoop(block(tokens, &["loop"], false)?.0),
+            "begin" => CalculatorInput::BeginUntil(block(tokens, &["until"], false)?.0),
+            token => input(token)?,
+        };
+
+        inputs.push(input);
+    }
+}
+
+fn input<N: Number>(token: &str) -> Option<CalculatorInput<N>> {
+    if let Some(input) = built_in(token)
+    {
+        return Some(input);
+    }
+    if let Ok(value) = token.parse()
+    {
+        return Some(CalculatorInput::Value(value));
+    }
+
+    match token.split_at_checked(1)
+    {
+        Some(("!", name)) if is_name(name) => Some(CalculatorInput::Store(name.to_string())),
+        Some(("@", name)) if is_name(name) => Some(CalculatorInput::Fetch(name.to_string())),
+        _ if is_name(token) && !KEYWORDS.contains(&token) => Some(CalculatorInput::Word(token.to_string())),
+        _ => None,
+    }
+}
+
+fn built_in<N>(token: &str) -> Option<CalculatorInput<N>> {
+    match token
+    {
+        "+" => Some(CalculatorInput::Add),
+        "-" => Some(CalculatorInput::Subtract),
+        "*" => Some(CalculatorInput::Multiply),
+        "/" => Some(CalculatorInput::Divide),
+        "%" | "mod" => Some(CalculatorInput::Modulo),
+        "^" | "pow" => Some(CalculatorInput::Power),
+        "neg" => Some(CalculatorInput::Negate),
+        "abs" => Some(CalculatorInput::Abs),
+        "min" => Some(CalculatorInput::Min),
+        "max" => Some(CalculatorInput::Max),
+        "=" => Some(CalculatorInput::Equal),
+        "<>" => Some(CalculatorInput::NotEqual),
+        "<" => Some(CalculatorInput::Less),
+        "<=" => Some(CalculatorInput::LessEqual),
+        ">" => Some(CalculatorInput::Greater),
+        ">=" => Some(CalculatorInput::GreaterEqual),
+        "dup" => Some(CalculatorInput::Dup),
+        "drop" => Some(CalculatorInput::Drop),
+        "swap" => Some(CalculatorInput::Swap),
+        "over" => Some(CalculatorInput::Over),
+        "rot" => Some(CalculatorInput::Rot),
+        "i" => Some(CalculatorInput::LoopIndex),
+        _ => None,
+    }
+}
+
+fn write_block<N: fmt::Display>(f: &mut fmt::Formatter<'_>, inputs: &[CalculatorInput<N>]) -> fmt::Result {
+    for input in inputs
+    {
+        write!(f, " {}", input)?;
+    }
+
+    Ok(())
+}
+
+/// Prints an input as the tokens `tokenize` reads it from.
+impl<N: fmt::Display> fmt::Display for CalculatorInput<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let token = match self
+        {
+            CalculatorInput::Add => "+",
+            CalculatorInput::Subtract => "-",
+            CalculatorInput::Multiply => "*",
+            CalculatorInput::Divide => "/",
+            CalculatorInput::Modulo => "%",
+            CalculatorInput::Power => "^",
+            CalculatorInput::Negate => "neg",
+            CalculatorInput::Abs => "abs",
+            CalculatorInput::Min => "min",
+            CalculatorInput::Max => "max",
+            CalculatorInput::Equal => "=",
+            CalculatorInput::NotEqual => "<>",
+            CalculatorInput::Less => "<",
+            CalculatorInput::LessEqual => "<=",
+            CalculatorInput::Greater => ">",
+            CalculatorInput::GreaterEqual => ">=",
+            CalculatorInput::Dup => "dup",
+            CalculatorInput::Drop => "drop",
+            CalculatorInput::Swap => "swap",
+            CalculatorInput::Over => "over",
+            CalculatorInput::Rot => "rot",
+            CalculatorInput::LoopIndex => "i",
+            CalculatorInput::Value(value) => return write!(f, "{}", value),
+            CalculatorInput::Word(name) => name,
+            CalculatorInput::Store(name) => return write!(f, "!{}", name),
+            CalculatorInput::Fetch(name) => return write!(f, "@{}", name),
+            CalculatorInput::Define(name, body) => {
+                write!(f, ": {}", name)?;
+                write_block(f, body)?;
+                return write!(f, " ;");
+            }
+            CalculatorInput::If(then, otherwise) => {
+                write!(f, "if")?;
+                write_block(f, then)?;
+                if !otherwise.is_empty()
+                {
+                    write!(f, " else")?;
+                    write_block(f, otherwise)?;
+                }
+                return write!(f, " then");
+            }
+            CalculatorInput::DoLoop(body) => {
+                write!(f, "do")?;
+                write_block(f, body)?;
+                return write!(f, " loop");
+            }
+            CalculatorInput::BeginUntil(body) => {
+                write!(f, "begin")?;
+                write_block(f, body)?;
+                return write!(f, " until");
+            }
+        };
+
+        write!(f, "{}", token)
+    }
+}
+
+fn is_name(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_alphabetic())
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// an operator waiting on the shunting-yard stack
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Power,
+    Negate,
+    LeftParenthesis,
+}
+
+impl Operator {
+    fn precedence(&self) -> u8 {
+        match self
+        {
+            Operator::Add | Operator::Subtract => 1,
+            Operator::Multiply | Operator::Divide | Operator::Modulo => 2,
+            Operator::Negate => 3,
+            Operator::Power => 4,
+            Operator::LeftParenthesis => 0,
+        }
+    }
+
+    fn is_right_associative(&self) -> bool {
+        *self == Operator::Power
+    }
+
+    fn output<N>(&self, output: &mut Vec<CalculatorInput<N>>) {
+        match self
+        {
+            Operator::Add => output.push(CalculatorInput::Add),
+            Operator::Subtract => output.push(CalculatorInput::Subtract),
+            Operator::Multiply => output.push(CalculatorInput::Multiply),
+            Operator::Divide => output.push(CalculatorInput::Divide),
+            Operator::Modulo => output.push(CalculatorInput::Modulo),
+            Operator::Power => output.push(CalculatorInput::Power),
+            Operator::Negate => output.push(CalculatorInput::Negate),
+            Operator::LeftParenthesis => {}
+        }
+    }
+}
+
+/// Converts an infix expression like `"(3 + 4) * 2"` to RPN with the shunting-yard algorithm.
+///
+/// `^` takes precedence over a `-` in front of an operand, which negates it, then come `*`, `/`
+/// and `%`, then `+` and `-`. `^` is right associative and the other operators are left
+/// associative. Spaces are optional.
+/// Returns `None` if the expression is not well formed.
+pub fn infix_to_rpn<N: Number>(s: &str) -> Option<Vec<CalculatorInput<N>>> {
+    let mut output: Vec<CalculatorInput<N>> = vec!();
+    let mut operators: Vec<Operator> = vec!();
+    // whether the next token must be an operand, or an operator
+    let mut expect_operand = true;
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next()
+    {
+        match (c, expect_operand)
+        {
+            (' ' | '\t' | '\n', _) => {}
+            ('0'..='9', true) => {
+                let mut number = c.to_string();
+                while let Some(digit) = chars.next_if(|c| c.is_ascii_digit() || *c == '.')
+                {
+                    number.push(digit);
+                }
+
+                output.push(CalculatorInput::Value(number.parse().ok()?));
+                expect_operand = false;
+            }
+            ('-', true) => operators.push(Operator::Negate),
+            ('(', true) => operators.push(Operator::LeftParenthesis),
+            (')', false) => {
+                loop
+                {
+                    match operators.pop()?
+                    {
+                        Operator::LeftParenthesis => break,
+                        operator => operator.output(&mut output),
+                    }
+                }
+            }
+            ('+' | '-' | '*' | '/' | '%' | '^', false) => {
+                let operator = match c
+                {
+                    '+' => Operator::Add,
+                    '-' => Operator::Subtract,
+                    '*' => Operator::Multiply,
+                    '/' => Operator::Divide,
+                    '%' => Operator::Modulo,
+                    _ => Operator::Power,
+                };
+
+                // the operators on the stack which bind more tightly are applied first,
+                // and so are the ones which bind as tightly for left associative operators
+                while let Some(top) = operators.pop_if(|top| {
+                    top.precedence() > operator.precedence()
+                        || (top.precedence() == operator.precedence() && !operator.is_right_associative())
+                })
+                {
+                    top.output(&mut output);
+                }
+
+                operators.push(operator);
+                expect_operand = true;
+            }
+            _ => return None,
+        }
+    }
+
+    if expect_operand
+    {
+        return None;
+    }
+
+    while let Some(operator) = operators.pop()
+    {
+        if operator == Operator::LeftParenthesis
+        {
+            return None;
+        }
+        operator.output(&mut output);
+    }
+
+    Some(output)
+}
+
+/// Evaluates an infix expression like `"(3 + 4) * 2"`.
+///
+/// Returns `None` if the expression is not well formed or cannot be evaluated;
+/// `infix_to_rpn` and `evaluate` tell which one failed.
+pub fn evaluate_infix<N: Number>(s: &str) -> Option<N> {
+    crate::evaluate(&infix_to_rpn(s)?).ok()
+}
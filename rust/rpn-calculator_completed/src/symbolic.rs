@@ -0,0 +1,422 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::dictionary::Dictionary;
+use crate::{CalculatorInput, CompileError, ErrorKind, Number};
+
+/// A formula, where the variables stand for any value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expression<N = i32> {
+    Value(N),
+    Variable(String),
+    Negate(Box<Expression<N>>),
+    Abs(Box<Expression<N>>),
+    Binary(BinaryOperator, Box<Expression<N>>, Box<Expression<N>>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Power,
+    Min,
+    Max,
+}
+
+impl BinaryOperator {
+    fn apply<N: Number>(&self, a: &N, b: &N) -> Result<N, ErrorKind> {
+        match self
+        {
+            BinaryOperator::Add => a.try_add(b),
+            BinaryOperator::Subtract => a.try_sub(b),
+            BinaryOperator::Multiply => a.try_mul(b),
+            BinaryOperator::Divide => a.try_div(b),
+            BinaryOperator::Modulo => a.try_rem(b),
+            BinaryOperator::Power => a.try_pow(b),
+            BinaryOperator::Min => Ok(if b < a { b.clone() } else { a.clone() }),
+            BinaryOperator::Max => Ok(if b > a { b.clone() } else { a.clone() }),
+        }
+    }
+
+    fn input<N>(&self) -> CalculatorInput<N> {
+        match self
+        {
+            BinaryOperator::Add => CalculatorInput::Add,
+            BinaryOperator::Subtract => CalculatorInput::Subtract,
+            BinaryOperator::Multiply => CalculatorInput::Multiply,
+            BinaryOperator::Divide => CalculatorInput::Divide,
+            BinaryOperator::Modulo => CalculatorInput::Modulo,
+            BinaryOperator::Power => CalculatorInput::Power,
+            BinaryOperator::Min => CalculatorInput::Min,
+            BinaryOperator::Max => CalculatorInput::Max,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self
+        {
+            BinaryOperator::Add => " + ",
+            BinaryOperator::Subtract => " - ",
+            BinaryOperator::Multiply => " * ",
+            BinaryOperator::Divide => " / ",
+            BinaryOperator::Modulo => " % ",
+            BinaryOperator::Power => "^",
+            BinaryOperator::Min => "min",
+            BinaryOperator::Max => "max",
+        }
+    }
+}
+
+fn binary<N>(operator: BinaryOperator, a: Expression<N>, b: Expression<N>) -> Expression<N> {
+    Expression::Binary(operator, Box::new(a), Box::new(b))
+}
+
+/// Turns RPN inputs into a formula. The variables which are fetched before anything is stored
+/// in them are left as variables, and the other ones are replaced by the formula stored in them.
+///
+/// The comparisons, the control structures and the loop index have no formula.
+pub fn symbolic<N: Number>(inputs: &[CalculatorInput<N>]) -> Result<Expression<N>, CompileError> {
+    let mut builder = Builder {
+        stack: vec!(),
+        dictionary: Dictionary::new(),
+        variables: HashMap::new(),
+    };
+
+    for (index, input) in inputs.iter().enumerate()
+    {
+        builder.build(input).map_err(|kind| CompileError { index, kind })?;
+    }
+
+    match builder.stack.len()
+    {
+        1 => Ok(builder.stack.remove(0)),
+        _ => Err(CompileError {
+            index: inputs.len(),
+            kind: ErrorKind::NoSingleResult,
+        }),
+    }
+}
+
+// runs inputs on a stack of formulas
+struct Builder<N> {
+    stack: Vec<Expression<N>>,
+    dictionary: Dictionary<N>,
+    variables: HashMap<String, Expression<N>>,
+}
+
+impl<N: Number> Builder<N> {
+    fn build(&mut self, input: &CalculatorInput<N>) -> Result<(), ErrorKind> {
+        let operator = match input
+        {
+            CalculatorInput::Define(name, body) => return self.dictionary.define(name, body),
+            CalculatorInput::Word(name) => {
+                for input in self.dictionary.word(name)?.iter()
+                {
+                    self.build(input)?;
+                }
+                return Ok(());
+            }
+            CalculatorInput::Value(value) => {
+                self.stack.push(Expression::Value(value.clone()));
+                return Ok(());
+            }
+            CalculatorInput::Fetch(name) => {
+                let variable = Expression::Variable(name.clone());
+                self.stack.push(self.variables.get(name).cloned().unwrap_or(variable));
+                return Ok(());
+            }
+            CalculatorInput::Store(name) => {
+                let expression = self.stack.pop().ok_or(ErrorKind::StackUnderflow)?;
+                self.variables.insert(name.clone(), expression);
+                return Ok(());
+            }
+            CalculatorInput::Add => BinaryOperator::Add,
+            CalculatorInput::Subtract => BinaryOperator::Subtract,
+            CalculatorInput::Multiply => BinaryOperator::Multiply,
+            CalculatorInput::Divide => BinaryOperator::Divide,
+            CalculatorInput::Modulo => BinaryOperator::Modulo,
+            CalculatorInput::Power => BinaryOperator::Power,
+            CalculatorInput::Min => BinaryOperator::Min,
+            CalculatorInput::Max => BinaryOperator::Max,
+            CalculatorInput::Negate
+            | CalculatorInput::Abs
+            | CalculatorInput::Dup
+            | CalculatorInput::Drop
+            | CalculatorInput::Swap
+            | CalculatorInput::Over
+            | CalculatorInput::Rot => return self.shuffle(input),
+            _ => return Err(ErrorKind::NotSymbolic),
+        };
+
+        if self.stack.len() < 2
+        {
+            return Err(ErrorKind::StackUnderflow);
+        }
+        let b = self.stack.pop().unwrap();
+        let a = self.stack.pop().unwrap();
+        self.stack.push(binary(operator, a, b));
+        Ok(())
+    }
+
+    // runs the unary operators and the stack words
+    fn shuffle(&mut self, input: &CalculatorInput<N>) -> Result<(), ErrorKind> {
+        if self.stack.len() < input.arity()
+        {
+            return Err(ErrorKind::StackUnderflow);
+        }
+
+        let operands = self.stack.split_off(self.stack.len() - input.arity());
+        let results = match (input, operands.as_slice())
+        {
+            (CalculatorInput::Negate, [a]) => vec!(Expression::Negate(Box::new(a.clone()))),
+            (CalculatorInput::Abs, [a]) => vec!(Expression::Abs(Box::new(a.clone()))),
+            (CalculatorInput::Dup, [a]) => vec!(a.clone(), a.clone()),
+            (CalculatorInput::Drop, [_]) => vec!(),
+            (CalculatorInput::Swap, [a, b]) => vec!(b.clone(), a.clone()),
+            (CalculatorInput::Over, [a, b]) => vec!(a.clone(), b.clone(), a.clone()),
+            (CalculatorInput::Rot, [a, b, c]) => vec!(b.clone(), c.clone(), a.clone()),
+            _ => unreachable!(), // `build` only gives these inputs, with as many operands as their arity
+        };
+
+        self.stack.extend(results);
+        Ok(())
+    }
+}
+
+impl<N: Number> Expression<N> {
+    /// Folds the constants, and removes the operations which do nothing, like `x * 1` or `x + 0`.
+    ///
+    /// The constants which cannot be folded, like `1 / 0`, are left as they are, and so are the
+    /// operands which could fail, like the `1 / x` in `1 / x * 0`.
+    pub fn simplify(&self) -> Expression<N> {
+        let zero = N::from_bool(false);
+        let one = N::from_bool(true);
+        let is = |expression: &Expression<N>, value: &N| matches!(expression, Expression::Value(v) if v == value);
+
+        match self
+        {
+            Expression::Value(_) | Expression::Variable(_) => self.clone(),
+            Expression::Negate(a) => match a.simplify()
+            {
+                Expression::Value(value) => match value.try_neg()
+                {
+                    Ok(negated) => Expression::Value(negated),
+                    Err(_) => Expression::Negate(Box::new(Expression::Value(value))),
+                },
+                Expression::Negate(a) => *a,
+                a => Expression::Negate(Box::new(a)),
+            },
+            Expression::Abs(a) => match a.simplify()
+            {
+                Expression::Value(value) => match value.try_abs()
+                {
+                    Ok(absolute) => Expression::Value(absolute),
+                    Err(_) => Expression::Abs(Box::new(Expression::Value(value))),
+                },
+                a => Expression::Abs(Box::new(a)),
+            },
+            Expression::Binary(operator, a, b) => {
+                let (a, b) = (a.simplify(), b.simplify());
+                if let (Expression::Value(x), Expression::Value(y)) = (&a, &b)
+                {
+                    if let Ok(value) = operator.apply(x, y)
+                    {
+                        return Expression::Value(value);
+                    }
+                }
+
+                match operator
+                {
+                    BinaryOperator::Add if is(&a, &zero) => b,
+                    BinaryOperator::Add | BinaryOperator::Subtract if is(&b, &zero) => a,
+                    BinaryOperator::Subtract if is(&a, &zero) => Expression::Negate(Box::new(b)).simplify(),
+                    BinaryOperator::Subtract if a == b && !a.can_fail() => Expression::Value(zero),
+                    BinaryOperator::Multiply if is(&a, &zero) && !b.can_fail() => Expression::Value(zero),
+                    BinaryOperator::Multiply if is(&b, &zero) && !a.can_fail() => Expression::Value(zero),
+                    BinaryOperator::Multiply if is(&a, &one) => b,
+                    BinaryOperator::Multiply | BinaryOperator::Divide if is(&b, &one) => a,
+                    BinaryOperator::Power if is(&b, &one) => a,
+                    BinaryOperator::Power if is(&b, &zero) && !a.can_fail() => Expression::Value(one),
+                    _ => binary(*operator, a, b),
+                }
+            }
+        }
+    }
+
+    /// The simplified derivative with respect to a variable.
+    ///
+    /// Returns `None` if the formula uses `%`, `abs`, `min` or `max` on the variable, or has it
+    /// in an exponent.
+    pub fn derivative(&self, variable: &str) -> Option<Expression<N>> {
+        Some(self.differentiate(variable)?.simplify())
+    }
+
+    fn differentiate(&self, variable: &str) -> Option<Expression<N>> {
+        if !self.contains(variable)
+        {
+            return Some(Expression::Value(N::from_bool(false)));
+        }
+
+        let derivative = match self
+        {
+            // the variable itself, since the other leaves do not contain it
+            Expression::Value(_) | Expression::Variable(_) => Expression::Value(N::from_bool(true)),
+            Expression::Negate(a) => Expression::Negate(Box::new(a.differentiate(variable)?)),
+            Expression::Abs(_) => return None,
+            Expression::Binary(operator, a, b) => {
+                let (a, b) = (a.as_ref().clone(), b.as_ref().clone());
+                match operator
+                {
+                    BinaryOperator::Add | BinaryOperator::Subtract => {
+                        binary(*operator, a.differentiate(variable)?, b.differentiate(variable)?)
+                    }
+                    BinaryOperator::Multiply => binary(
+                        BinaryOperator::Add,
+                        binary(BinaryOperator::Multiply, a.differentiate(variable)?, b.clone()),
+                        binary(BinaryOperator::Multiply, a, b.differentiate(variable)?),
+                    ),
+                    BinaryOperator::Divide => binary(
+                        BinaryOperator::Divide,
+                        binary(
+                            BinaryOperator::Subtract,
+                            binary(BinaryOperator::Multiply, a.differentiate(variable)?, b.clone()),
+                            binary(BinaryOperator::Multiply, a, b.differentiate(variable)?),
+                        ),
+                        binary(BinaryOperator::Multiply, b.clone(), b),
+                    ),
+                    BinaryOperator::Power if !b.contains(variable) => {
+                        let da = a.differentiate(variable)?;
+                        let exponent = binary(BinaryOperator::Subtract, b.clone(), Expression::Value(N::from_bool(true)));
+                        binary(
+                            BinaryOperator::Multiply,
+                            binary(BinaryOperator::Multiply, b, binary(BinaryOperator::Power, a, exponent)),
+                            da,
+                        )
+                    }
+                    _ => return None,
+                }
+            }
+        };
+
+        Some(derivative)
+    }
+
+    fn contains(&self, variable: &str) -> bool {
+        match self
+        {
+            Expression::Value(_) => false,
+            Expression::Variable(name) => name == variable,
+            Expression::Negate(a) | Expression::Abs(a) => a.contains(variable),
+            Expression::Binary(_, a, b) => a.contains(variable) || b.contains(variable),
+        }
+    }
+
+    // whether the formula divides or raises to a power, which fails for some values
+    fn can_fail(&self) -> bool {
+        match self
+        {
+            Expression::Value(_) | Expression::Variable(_) => false,
+            Expression::Negate(a) | Expression::Abs(a) => a.can_fail(),
+            Expression::Binary(BinaryOperator::Divide | BinaryOperator::Modulo | BinaryOperator::Power, _, _) => true,
+            Expression::Binary(_, a, b) => a.can_fail() || b.can_fail(),
+        }
+    }
+
+    /// The RPN inputs computing the formula, which fetch its variables.
+    pub fn to_rpn(&self) -> Vec<CalculatorInput<N>> {
+        let mut inputs = vec!();
+        self.lower(&mut inputs);
+        inputs
+    }
+
+    fn lower(&self, inputs: &mut Vec<CalculatorInput<N>>) {
+        match self
+        {
+            Expression::Value(value) => inputs.push(CalculatorInput::Value(value.clone())),
+            Expression::Variable(name) => inputs.push(CalculatorInput::Fetch(name.clone())),
+            Expression::Negate(a) => {
+                a.lower(inputs);
+                inputs.push(CalculatorInput::Negate);
+            }
+            Expression::Abs(a) => {
+                a.lower(inputs);
+                inputs.push(CalculatorInput::Abs);
+            }
+            Expression::Binary(operator, a, b) => {
+                a.lower(inputs);
+                b.lower(inputs);
+                inputs.push(operator.input());
+            }
+        }
+    }
+
+    // how tightly the formula binds when it is printed, with the same precedences as
+    // `infix_to_rpn`, and the functions and the leaves binding the most tightly
+    fn precedence(&self) -> u8 {
+        match self
+        {
+            // the negative values are printed like a negation, and the fractions like a division
+            Expression::Value(value) => {
+                let text = value.to_string();
+                match (text.starts_with('-'), text.contains('/'))
+                {
+                    (true, _) => 3,
+                    (false, true) => 2,
+                    _ => 5,
+                }
+            }
+            Expression::Negate(_) => 3,
+            Expression::Binary(BinaryOperator::Add | BinaryOperator::Subtract, ..) => 1,
+            Expression::Binary(BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo, ..) => 2,
+            Expression::Binary(BinaryOperator::Power, ..) => 4,
+            _ => 5,
+        }
+    }
+}
+
+fn write_operand<N: Number>(f: &mut fmt::Formatter<'_>, operand: &Expression<N>, parenthesized: bool) -> fmt::Result {
+    match parenthesized
+    {
+        true => write!(f, "({})", operand),
+        false => write!(f, "{}", operand),
+    }
+}
+
+/// Prints the formula in infix notation, with only the parentheses it needs.
+impl<N: Number> fmt::Display for Expression<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self
+        {
+            Expression::Value(value) => write!(f, "{}", value),
+            Expression::Variable(name) => write!(f, "{}", name),
+            Expression::Negate(a) => {
+                write!(f, "-")?;
+                write_operand(f, a, a.precedence() < 3)
+            }
+            Expression::Abs(a) => write!(f, "abs({})", a),
+            Expression::Binary(operator @ (BinaryOperator::Min | BinaryOperator::Max), a, b) => {
+                write!(f, "{}({}, {})", operator.symbol(), a, b)
+            }
+            Expression::Binary(operator, a, b) => {
+                let precedence = self.precedence();
+                // `^` is right associative, and the other operators are left associative,
+                // except that `a + (b - c)` is `a + b - c` and `a * (b * c)` is `a * b * c`
+                let (left, right) = match (operator, b.as_ref())
+                {
+                    (BinaryOperator::Power, _) => (a.precedence() <= precedence, b.precedence() < precedence),
+                    (BinaryOperator::Add, _) | (BinaryOperator::Multiply, Expression::Binary(BinaryOperator::Multiply, ..)) => {
+                        (a.precedence() < precedence, b.precedence() < precedence)
+                    }
+                    _ => (a.precedence() < precedence, b.precedence() <= precedence),
+                };
+
+                write_operand(f, a, left)?;
+                write!(f, "{}", operator.symbol())?;
+                write_operand(f, b, right)
+            }
+        }
+    }
+}
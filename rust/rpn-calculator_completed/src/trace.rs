@@ -0,0 +1,107 @@
+use std::fmt;
+
+use crate::dictionary::Context;
+use crate::{evaluate_in, CalculatorError, CalculatorInput, ErrorKind, Number, DEFAULT_STEP_LIMIT};
+
+/// An input which was run: the index of the input given to the evaluation which ran it, how
+/// many words and control structures it is in, and the stack before and after it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Step<N = i32> {
+    pub index: usize,
+    pub depth: usize,
+    pub input: CalculatorInput<N>,
+    pub before: Vec<N>,
+    pub after: Result<Vec<N>, ErrorKind>,
+}
+
+/// What `debug` does after calling back.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    /// Runs up to the next breakpoint.
+    Continue,
+    /// Runs a single step, and calls back again.
+    Step,
+    /// Stops the evaluation, which fails with `ErrorKind::Interrupted`.
+    Abort,
+}
+
+/// The steps of an evaluation, and its result.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trace<N = i32> {
+    pub steps: Vec<Step<N>>,
+    pub result: Result<N, CalculatorError<N>>,
+}
+
+/// Evaluates inputs like `evaluate`, recording every step.
+///
+/// The words and the control structures are not steps themselves, but the inputs they run are.
+pub fn trace<N: Number>(inputs: &[CalculatorInput<N>]) -> Trace<N> {
+    debug(inputs, &[], |_| Command::Continue)
+}
+
+/// Evaluates inputs like `trace`, calling back after each step of the inputs at the
+/// breakpoints, which are indices in `inputs`, and after the next step whenever the callback
+/// returns `Command::Step`.
+pub fn debug<N: Number>(
+    inputs: &[CalculatorInput<N>],
+    breakpoints: &[usize],
+    mut callback: impl FnMut(&Step<N>) -> Command,
+) -> Trace<N> {
+    let mut steps: Vec<Step<N>> = vec!();
+    let mut stepping = false;
+
+    let mut observer = |step: Step<N>| {
+        let mut go_on = true;
+        if stepping || breakpoints.contains(&step.index)
+        {
+            match callback(&step)
+            {
+                Command::Continue => stepping = false,
+                Command::Step => stepping = true,
+                Command::Abort => go_on = false,
+            }
+        }
+
+        steps.push(step);
+        go_on
+    };
+    let result = evaluate_in(inputs, &mut Context::new(DEFAULT_STEP_LIMIT, Some(&mut observer)));
+
+    Trace { steps, result }
+}
+
+fn stack<N: fmt::Display>(stack: &[N]) -> String {
+    let values: Vec<String> = stack.iter().map(N::to_string).collect();
+    values.join(" ")
+}
+
+/// Prints the steps as a table, with the inputs run by words and control structures indented,
+/// followed by the result.
+impl<N: fmt::Display> fmt::Display for Trace<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut rows: Vec<[String; 4]> = vec!(["step", "input", "before", "after"].map(String::from));
+        for (i, step) in self.steps.iter().enumerate()
+        {
+            let after = match &step.after
+            {
+                Ok(after) => stack(after),
+                Err(kind) => format!("error: {}", kind),
+            };
+            rows.push([(i + 1).to_string(), format!("{}{}", "  ".repeat(step.depth), step.input), stack(&step.before), after]);
+        }
+
+        let width = |column: usize| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0);
+        let (step, input, before) = (width(0), width(1), width(2));
+        for row in &rows
+        {
+            let line = format!("{:>step$}  {:<input$}  {:<before$}  {}", row[0], row[1], row[2], row[3]);
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        match &self.result
+        {
+            Ok(value) => write!(f, "result: {}", value),
+            Err(error) => write!(f, "error: {}", error),
+        }
+    }
+}
@@ -0,0 +1,504 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::dictionary::Dictionary;
+use crate::{CalculatorError, CalculatorInput, ErrorKind, Number, DEFAULT_STEP_LIMIT};
+
+/// A bytecode instruction. The values and the variables are referred to by their index in the
+/// constants and in the slots of the program, and the jumps by the index of an instruction.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Instruction {
+    Constant(u16),
+    Load(u16),
+    Store(u16),
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Power,
+    Negate,
+    Abs,
+    Min,
+    Max,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Dup,
+    Drop,
+    Swap,
+    Over,
+    Rot,
+    Jump(u16),
+    /// Takes a flag, and jumps when it is false.
+    JumpIfFalse(u16),
+    /// Takes a start and a limit, and jumps past the loop when there is nothing to loop over.
+    DoLoop(u16),
+    /// Increments the loop index, and jumps back to the start of the loop until the limit.
+    Loop(u16),
+    LoopIndex,
+}
+
+/// Inputs compiled to bytecode, which can be run many times with different parameters.
+///
+/// The parameters are the variables fetched before anything is stored in them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Program<N = i32> {
+    bytecode: Vec<Instruction>,
+    constants: Vec<N>,
+    parameters: Vec<String>,
+    locals: usize,
+    // the index of the input each instruction was compiled from
+    sources: Vec<usize>,
+    max_depth: usize,
+    step_limit: usize,
+}
+
+/// Why inputs could not be compiled to bytecode or to a formula: the index of the input at fault,
+/// and what is wrong with it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CompileError {
+    pub index: usize,
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at input {}", self.kind, self.index)
+    }
+}
+
+impl Error for CompileError {}
+
+impl Instruction {
+    // how many values the instruction takes from the stack, and how many it puts back
+    fn stack_effect(&self) -> (usize, usize) {
+        match self
+        {
+            Instruction::Constant(_) | Instruction::Load(_) | Instruction::LoopIndex => (0, 1),
+            Instruction::Jump(_) | Instruction::Loop(_) => (0, 0),
+            Instruction::Store(_) | Instruction::Drop | Instruction::JumpIfFalse(_) => (1, 0),
+            Instruction::Negate | Instruction::Abs => (1, 1),
+            Instruction::Dup => (1, 2),
+            Instruction::DoLoop(_) => (2, 0),
+            Instruction::Swap => (2, 2),
+            Instruction::Over => (2, 3),
+            Instruction::Rot => (3, 3),
+            _ => (2, 1),
+        }
+    }
+}
+
+// builds a program, keeping track of the words and of the stack depth
+struct Compiler<N> {
+    program: Program<N>,
+    dictionary: Dictionary<N>,
+    slots: HashMap<String, u16>,
+    // the local variables, which are stored before being fetched
+    locals: Vec<String>,
+    depth: usize,
+    // how many loops the instructions being compiled are in
+    loops: usize,
+}
+
+/// Compiles inputs to bytecode, checking that no input takes more values than there are on the
+/// stack and that exactly one value is left at the end.
+///
+/// The words are replaced by their bodies, so the words defined by the inputs only last for
+/// the compilation. The branches of an `If` must leave as many values on the stack, and the
+/// loops must leave the stack as they found it, flag of `BeginUntil` aside.
+pub fn compile<N: Number>(inputs: &[CalculatorInput<N>]) -> Result<Program<N>, CompileError> {
+    let mut compiler = Compiler {
+        program: Program {
+            bytecode: vec!(),
+            constants: vec!(),
+            parameters: vec!(),
+            locals: 0,
+            sources: vec!(),
+            max_depth: 0,
+            step_limit: DEFAULT_STEP_LIMIT,
+        },
+        dictionary: Dictionary::new(),
+        slots: HashMap::new(),
+        locals: vec!(),
+        depth: 0,
+        loops: 0,
+    };
+
+    for (index, input) in inputs.iter().enumerate()
+    {
+        compiler.compile(input, index)?;
+    }
+
+    if compiler.depth != 1
+    {
+        return Err(CompileError {
+            index: inputs.len(),
+            kind: ErrorKind::NoSingleResult,
+        });
+    }
+
+    // the locals come after the parameters, in the order they are first stored
+    let mut program = compiler.program;
+    let parameters = operand(program.parameters.len(), inputs.len())?;
+    let first_local = u16::MAX - operand(compiler.locals.len(), inputs.len())?;
+    for instruction in program.bytecode.iter_mut()
+    {
+        if let Instruction::Load(slot) | Instruction::Store(slot) = instruction
+        {
+            if *slot > first_local
+            {
+                *slot = parameters + (u16::MAX - *slot);
+            }
+        }
+    }
+    program.locals = compiler.locals.len();
+
+    Ok(program)
+}
+
+impl<N: Number> Compiler<N> {
+    fn compile(&mut self, input: &CalculatorInput<N>, index: usize) -> Result<(), CompileError> {
+        let error = |kind| CompileError { index, kind };
+
+        let instruction = match input
+        {
+            CalculatorInput::Define(name, body) => return self.dictionary.define(name, body).map_err(error),
+            CalculatorInput::Word(name) => {
+                let body = self.dictionary.word(name).map_err(error)?;
+                return self.compile_all(&body, index);
+            }
+            CalculatorInput::If(then, otherwise) => {
+                let branch = self.emit(Instruction::JumpIfFalse(0), index)?;
+                let depth = self.depth;
+                self.compile_all(then, index)?;
+                let then_depth = self.depth;
+                let jump = self.emit(Instruction::Jump(0), index)?;
+
+                self.patch(branch, index)?;
+                self.depth = depth;
+                self.compile_all(otherwise, index)?;
+                if self.depth != then_depth
+                {
+                    return Err(error(ErrorKind::UnbalancedStack));
+                }
+
+                self.patch(jump, index)?;
+                return Ok(());
+            }
+            CalculatorInput::DoLoop(body) => {
+                let start = self.emit(Instruction::DoLoop(0), index)?;
+                let depth = self.depth;
+                self.loops += 1;
+                self.compile_all(body, index)?;
+                self.loops -= 1;
+                if self.depth != depth
+                {
+                    return Err(error(ErrorKind::UnbalancedStack));
+                }
+
+                self.emit(Instruction::Loop(operand(start + 1, index)?), index)?;
+                self.patch(start, index)?;
+                return Ok(());
+            }
+            CalculatorInput::BeginUntil(body) => {
+                let start = self.program.bytecode.len();
+                let depth = self.depth;
+                self.compile_all(body, index)?;
+                if self.depth != depth + 1
+                {
+                    return Err(error(ErrorKind::UnbalancedStack));
+                }
+
+                self.emit(Instruction::JumpIfFalse(operand(start, index)?), index)?;
+                return Ok(());
+            }
+            CalculatorInput::LoopIndex if self.loops == 0 => return Err(error(ErrorKind::NoLoop)),
+            CalculatorInput::LoopIndex => Instruction::LoopIndex,
+            CalculatorInput::Value(value) => {
+                self.program.constants.push(value.clone());
+                Instruction::Constant(operand(self.program.constants.len() - 1, index)?)
+            }
+            CalculatorInput::Fetch(name) => Instruction::Load(self.slot(name, false, index)?),
+            CalculatorInput::Store(name) => Instruction::Store(self.slot(name, true, index)?),
+            CalculatorInput::Add => Instruction::Add,
+            CalculatorInput::Subtract => Instruction::Subtract,
+            CalculatorInput::Multiply => Instruction::Multiply,
+            CalculatorInput::Divide => Instruction::Divide,
+            CalculatorInput::Modulo => Instruction::Modulo,
+            CalculatorInput::Power => Instruction::Power,
+            CalculatorInput::Negate => Instruction::Negate,
+            CalculatorInput::Abs => Instruction::Abs,
+            CalculatorInput::Min => Instruction::Min,
+            CalculatorInput::Max => Instruction::Max,
+            CalculatorInput::Equal => Instruction::Equal,
+            CalculatorInput::NotEqual => Instruction::NotEqual,
+            CalculatorInput::Less => Instruction::Less,
+            CalculatorInput::LessEqual => Instruction::LessEqual,
+            CalculatorInput::Greater => Instruction::Greater,
+            CalculatorInput::GreaterEqual => Instruction::GreaterEqual,
+            CalculatorInput::Dup => Instruction::Dup,
+            CalculatorInput::Drop => Instruction::Drop,
+            CalculatorInput::Swap => Instruction::Swap,
+            CalculatorInput::Over => Instruction::Over,
+            CalculatorInput::Rot => Instruction::Rot,
+        };
+
+        self.emit(instruction, index)?;
+        Ok(())
+    }
+
+    fn compile_all(&mut self, inputs: &[CalculatorInput<N>], index: usize) -> Result<(), CompileError> {
+        for input in inputs
+        {
+            self.compile(input, index)?;
+        }
+
+        Ok(())
+    }
+
+    // adds an instruction after checking the stack depth, and returns its position
+    fn emit(&mut self, instruction: Instruction, index: usize) -> Result<usize, CompileError> {
+        let (pops, pushes) = instruction.stack_effect();
+        if self.depth < pops
+        {
+            return Err(CompileError {
+                index,
+                kind: ErrorKind::StackUnderflow,
+            });
+        }
+        self.depth = self.depth - pops + pushes;
+        self.program.max_depth = self.program.max_depth.max(self.depth);
+
+        self.program.bytecode.push(instruction);
+        self.program.sources.push(index);
+        Ok(self.program.bytecode.len() - 1)
+    }
+
+    // makes the jump at a position go to the next instruction to be compiled
+    fn patch(&mut self, position: usize, index: usize) -> Result<(), CompileError> {
+        let next = operand(self.program.bytecode.len(), index)?;
+        match &mut self.program.bytecode[position]
+        {
+            Instruction::Jump(target) | Instruction::JumpIfFalse(target) | Instruction::DoLoop(target) => *target = next,
+            _ => unreachable!(), // only the jumps are patched
+        }
+
+        Ok(())
+    }
+
+    // the slot of a variable: the parameters are numbered from 0, and the locals from the end
+    // until every parameter is known
+    fn slot(&mut self, name: &str, store: bool, index: usize) -> Result<u16, CompileError> {
+        if let Some(&slot) = self.slots.get(name)
+        {
+            return Ok(slot);
+        }
+
+        // the parameters and the locals share the slots
+        operand(self.program.parameters.len() + self.locals.len() + 1, index)?;
+
+        let slot = match store
+        {
+            true => {
+                self.locals.push(name.to_string());
+                u16::MAX - operand(self.locals.len() - 1, index)?
+            }
+            false => {
+                self.program.parameters.push(name.to_string());
+                operand(self.program.parameters.len() - 1, index)?
+            }
+        };
+
+        self.slots.insert(name.to_string(), slot);
+        Ok(slot)
+    }
+}
+
+// an operand of an instruction, which fails to compile when the program is too large for it
+fn operand(value: usize, index: usize) -> Result<u16, CompileError> {
+    u16::try_from(value).map_err(|_| CompileError {
+        index,
+        kind: ErrorKind::ProgramTooLarge,
+    })
+}
+
+impl<N: Number> Program<N> {
+    pub fn bytecode(&self) -> &[Instruction] {
+        &self.bytecode
+    }
+
+    /// The names of the parameters, in the order `run` takes them.
+    pub fn parameters(&self) -> &[String] {
+        &self.parameters
+    }
+
+    /// Sets the amount of instructions a run may execute, `DEFAULT_STEP_LIMIT` by default.
+    pub fn set_step_limit(&mut self, step_limit: usize) {
+        self.step_limit = step_limit;
+    }
+
+    /// Runs the program with the values of its parameters.
+    ///
+    /// The errors hold the index of the input the failing instruction was compiled from, and
+    /// the stack when it failed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the amount of values does not match the amount of parameters.
+    pub fn run(&self, parameters: &[N]) -> Result<N, CalculatorError<N>> {
+        assert_eq!(parameters.len(), self.parameters.len(), "wrong amount of parameters");
+
+        let mut stack: Vec<N> = Vec::with_capacity(self.max_depth);
+        // a local is only set once something is stored in it, which a branch may skip
+        let mut slots: Vec<Option<N>> = parameters.iter().cloned().map(Some).collect();
+        slots.resize(parameters.len() + self.locals, None);
+        // the index and the limit of the loops being run, the innermost one last
+        let mut loops: Vec<(N, N)> = vec!();
+        let mut steps = self.step_limit;
+        let mut pc = 0;
+
+        // the unwraps are safe because the stack depth was checked by the compiler
+        while let Some(instruction) = self.bytecode.get(pc)
+        {
+            if steps == 0
+            {
+                return Err(CalculatorError {
+                    index: self.sources[pc],
+                    kind: ErrorKind::StepLimitExceeded,
+                    stack,
+                });
+            }
+            steps -= 1;
+            pc += 1;
+
+            let result = match instruction
+            {
+                Instruction::Constant(constant) => {
+                    stack.push(self.constants[*constant as usize].clone());
+                    continue;
+                }
+                Instruction::Load(slot) => match &slots[*slot as usize]
+                {
+                    Some(value) => {
+                        stack.push(value.clone());
+                        continue;
+                    }
+                    None => Err(ErrorKind::UnknownVariable),
+                },
+                Instruction::Store(slot) => {
+                    slots[*slot as usize] = stack.pop();
+                    continue;
+                }
+                Instruction::Dup => {
+                    stack.push(stack.last().unwrap().clone());
+                    continue;
+                }
+                Instruction::Drop => {
+                    stack.pop();
+                    continue;
+                }
+                Instruction::Swap => {
+                    let len = stack.len();
+                    stack.swap(len - 1, len - 2);
+                    continue;
+                }
+                Instruction::Over => {
+                    stack.push(stack[stack.len() - 2].clone());
+                    continue;
+                }
+                Instruction::Rot => {
+                    let len = stack.len();
+                    stack[len - 3..].rotate_left(1);
+                    continue;
+                }
+                Instruction::Jump(target) => {
+                    pc = *target as usize;
+                    continue;
+                }
+                Instruction::JumpIfFalse(target) => {
+                    if !stack.pop().unwrap().is_true()
+                    {
+                        pc = *target as usize;
+                    }
+                    continue;
+                }
+                Instruction::DoLoop(end) => {
+                    let start = stack.pop().unwrap();
+                    let limit = stack.pop().unwrap();
+                    match start < limit
+                    {
+                        true => loops.push((start, limit)),
+                        false => pc = *end as usize,
+                    }
+                    continue;
+                }
+                Instruction::Loop(body) => {
+                    let (index, limit) = loops.last_mut().unwrap();
+                    match index.try_add(&N::from_bool(true))
+                    {
+                        Ok(next) => {
+                            *index = next;
+                            match *index < *limit
+                            {
+                                true => pc = *body as usize,
+                                false => drop(loops.pop()),
+                            }
+                            continue;
+                        }
+                        Err(kind) => Err(kind),
+                    }
+                }
+                Instruction::LoopIndex => {
+                    stack.push(loops.last().unwrap().0.clone());
+                    continue;
+                }
+                Instruction::Negate => stack.last().unwrap().try_neg(),
+                Instruction::Abs => stack.last().unwrap().try_abs(),
+                _ => {
+                    let (a, b) = (&stack[stack.len() - 2], &stack[stack.len() - 1]);
+                    match instruction
+                    {
+                        Instruction::Add => a.try_add(b),
+                        Instruction::Subtract => a.try_sub(b),
+                        Instruction::Multiply => a.try_mul(b),
+                        Instruction::Divide => a.try_div(b),
+                        Instruction::Modulo => a.try_rem(b),
+                        Instruction::Power => a.try_pow(b),
+                        Instruction::Min => Ok(if b < a { b.clone() } else { a.clone() }),
+                        Instruction::Max => Ok(if b > a { b.clone() } else { a.clone() }),
+                        Instruction::Equal => Ok(N::from_bool(a == b)),
+                        Instruction::NotEqual => Ok(N::from_bool(a != b)),
+                        Instruction::Less => Ok(N::from_bool(a < b)),
+                        Instruction::LessEqual => Ok(N::from_bool(a <= b)),
+                        Instruction::Greater => Ok(N::from_bool(a > b)),
+                        Instruction::GreaterEqual => Ok(N::from_bool(a >= b)),
+                        _ => unreachable!(), // the other instructions were handled above
+                    }
+                }
+            };
+
+            match result
+            {
+                Ok(value) => {
+                    let (pops, _) = instruction.stack_effect();
+                    stack.truncate(stack.len() - pops);
+                    stack.push(value);
+                }
+                Err(kind) => {
+                    return Err(CalculatorError {
+                        index: self.sources[pc - 1],
+                        kind,
+                        stack,
+                    });
+                }
+            }
+        }
+
+        Ok(stack.pop().unwrap())
+    }
+}
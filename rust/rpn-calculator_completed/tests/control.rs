@@ -0,0 +1,174 @@
+use rpn_calculator::*;
+
+fn evaluate_str(s: &str) -> Result<i32, CalculatorError> {
+    evaluate(&tokenize(s).unwrap())
+}
+
+#[test]
+fn test_comparisons() {
+    assert_eq!(evaluate_str("3 3 ="), Ok(1));
+    assert_eq!(evaluate_str("3 4 ="), Ok(0));
+    assert_eq!(evaluate_str("3 4 <>"), Ok(1));
+    assert_eq!(evaluate_str("3 4 <"), Ok(1));
+    assert_eq!(evaluate_str("4 4 <="), Ok(1));
+    assert_eq!(evaluate_str("3 4 >"), Ok(0));
+    assert_eq!(evaluate_str("3 4 >="), Ok(0));
+}
+
+#[test]
+fn test_comparisons_with_other_numbers() {
+    assert_eq!(evaluate(&tokenize::<f64>("0.5 0.25 >").unwrap()), Ok(1.0));
+    assert_eq!(
+        evaluate(&tokenize::<Rational>("1/3 2/6 =").unwrap()),
+        Ok(Rational::from_integer(1.into()))
+    );
+}
+
+#[test]
+fn test_tokenize_if() {
+    assert_eq!(
+        tokenize("0 if 1 else 2 then"),
+        Some(vec![
+            CalculatorInput::Value(0),
+            CalculatorInput::If(vec![CalculatorInput::Value(1)], vec![CalculatorInput::Value(2)]),
+        ])
+    );
+    assert_eq!(
+        tokenize("1 if 2 then"),
+        Some(vec![
+            CalculatorInput::Value(1),
+            CalculatorInput::If(vec![CalculatorInput::Value(2)], vec![]),
+        ])
+    );
+}
+
+#[test]
+fn test_tokenize_loops() {
+    assert_eq!(
+        tokenize("3 0 do i drop loop begin 1 until"),
+        Some(vec![
+            CalculatorInput::Value(3),
+            CalculatorInput::Value(0),
+            CalculatorInput::DoLoop(vec![CalculatorInput::LoopIndex, CalculatorInput::Drop]),
+            CalculatorInput::BeginUntil(vec![CalculatorInput::Value(1)]),
+        ])
+    );
+}
+
+#[test]
+fn test_tokenize_malformed_control_structures() {
+    assert_eq!(tokenize::<i32>("1 if 2"), None);
+    assert_eq!(tokenize::<i32>("1 if 2 else 3"), None);
+    assert_eq!(tokenize::<i32>("1 then"), None);
+    assert_eq!(tokenize::<i32>("1 if 2 loop"), None);
+    assert_eq!(tokenize::<i32>("3 0 do i"), None);
+    assert_eq!(tokenize::<i32>("begin 1 until until"), None);
+    assert_eq!(tokenize::<i32>("1 if : a 1 ; then"), None);
+    assert_eq!(tokenize::<i32>(": then 1 ;"), None);
+}
+
+#[test]
+fn test_if_else() {
+    assert_eq!(evaluate_str("1 if 10 else 20 then"), Ok(10));
+    assert_eq!(evaluate_str("0 if 10 else 20 then"), Ok(20));
+    assert_eq!(evaluate_str("5 -3 if 1 + then"), Ok(6));
+    assert_eq!(evaluate_str("5 0 if 1 + then"), Ok(5));
+    assert_eq!(evaluate_str("2 1 if then"), Ok(2));
+}
+
+#[test]
+fn test_nested_if() {
+    let sign = ": sign dup 0 < if drop -1 else 0 > if 1 else 0 then then ;";
+
+    assert_eq!(evaluate_str(&format!("{} -7 sign", sign)), Ok(-1));
+    assert_eq!(evaluate_str(&format!("{} 0 sign", sign)), Ok(0));
+    assert_eq!(evaluate_str(&format!("{} 7 sign", sign)), Ok(1));
+}
+
+#[test]
+fn test_do_loop() {
+    assert_eq!(evaluate_str("0 5 0 do i + loop"), Ok(10));
+    assert_eq!(evaluate_str("1 4 1 do i * loop"), Ok(6));
+    assert_eq!(evaluate_str("7 0 0 do 1 + loop"), Ok(7));
+    assert_eq!(evaluate_str("7 0 3 do 1 + loop"), Ok(7));
+}
+
+#[test]
+fn test_nested_loops_use_the_innermost_index() {
+    assert_eq!(evaluate_str("0 3 0 do 3 0 do i + loop loop"), Ok(9));
+    assert_eq!(evaluate_str("0 3 1 do 2 0 do loop i + loop"), Ok(3));
+}
+
+#[test]
+fn test_begin_until() {
+    assert_eq!(evaluate_str("1 begin 2 * dup 100 > until"), Ok(128));
+    assert_eq!(evaluate_str("0 !n begin @n 1 + !n @n 10 = until @n"), Ok(10));
+}
+
+#[test]
+fn test_factorial() {
+    assert_eq!(evaluate_str(": fact 1 swap 1 + 1 do i * loop ; 10 fact"), Ok(3628800));
+}
+
+#[test]
+fn test_loop_index_outside_of_a_loop() {
+    assert_eq!(
+        evaluate_str("1 i"),
+        Err(CalculatorError {
+            index: 1,
+            kind: ErrorKind::NoLoop,
+            stack: vec![1]
+        })
+    );
+}
+
+#[test]
+fn test_failing_control_structure_leaves_the_stack_untouched() {
+    assert_eq!(
+        evaluate_str("9 3 0 do 1 + i 2 = if 0 / then loop"),
+        Err(CalculatorError {
+            index: 3,
+            kind: ErrorKind::DivisionByZero,
+            stack: vec![9, 3, 0]
+        })
+    );
+    assert_eq!(
+        evaluate_str("if 1 then").unwrap_err().kind,
+        ErrorKind::StackUnderflow
+    );
+}
+
+#[test]
+fn test_runaway_loop_hits_the_step_limit() {
+    assert_eq!(
+        evaluate_str("1 begin 0 until"),
+        Err(CalculatorError {
+            index: 1,
+            kind: ErrorKind::StepLimitExceeded,
+            stack: vec![1]
+        })
+    );
+}
+
+#[test]
+fn test_step_limit() {
+    let mut calculator: Calculator = Calculator::new();
+    calculator.set_step_limit(10);
+
+    // the loop counts for one step, and each iteration for two more
+    assert!(calculator.run(&tokenize("0 3 0 do i + loop").unwrap()).is_ok());
+    assert_eq!(calculator.stack(), &[3]);
+    assert_eq!(
+        calculator.run(&tokenize("4 0 do i + loop").unwrap()).unwrap_err().kind,
+        ErrorKind::StepLimitExceeded
+    );
+    assert_eq!(calculator.stack(), &[3, 4, 0]);
+}
+
+#[test]
+fn test_redefinition_does_not_change_compiled_control_structures() {
+    assert_eq!(
+        evaluate_str(": one 1 ; : f 1 if one then ; : one 2 ; f"),
+        Ok(1)
+    );
+}
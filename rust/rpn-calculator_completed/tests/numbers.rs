@@ -0,0 +1,161 @@
+use rpn_calculator::*;
+
+fn evaluate_str<N: Number>(s: &str) -> Result<N, CalculatorError<N>> {
+    evaluate(&tokenize(s).unwrap())
+}
+
+#[test]
+fn test_modulo() {
+    assert_eq!(evaluate_str::<i32>("17 5 %"), Ok(2));
+    assert_eq!(evaluate_str::<i32>("-7 2 %"), Ok(-1));
+}
+
+#[test]
+fn test_power() {
+    assert_eq!(evaluate_str::<i32>("2 10 ^"), Ok(1024));
+    assert_eq!(evaluate_str::<i32>("5 0 ^"), Ok(1));
+}
+
+#[test]
+fn test_integer_power_needs_a_positive_exponent() {
+    assert_eq!(
+        evaluate_str::<i32>("2 -1 ^").unwrap_err().kind,
+        ErrorKind::InvalidExponent
+    );
+}
+
+#[test]
+fn test_power_overflow() {
+    assert_eq!(
+        evaluate_str::<i32>("2 31 ^").unwrap_err().kind,
+        ErrorKind::Overflow
+    );
+    assert_eq!(evaluate_str::<i64>("2 31 ^"), Ok(2147483648));
+}
+
+#[test]
+fn test_negate_and_abs() {
+    assert_eq!(evaluate_str::<i32>("3 neg"), Ok(-3));
+    assert_eq!(evaluate_str::<i32>("-3 abs"), Ok(3));
+    assert_eq!(
+        evaluate_str::<i32>("-2147483648 abs").unwrap_err().kind,
+        ErrorKind::Overflow
+    );
+}
+
+#[test]
+fn test_min_and_max() {
+    assert_eq!(evaluate_str::<i32>("3 7 min"), Ok(3));
+    assert_eq!(evaluate_str::<i32>("3 7 max"), Ok(7));
+}
+
+#[test]
+fn test_stack_words() {
+    assert_eq!(evaluate_str::<i32>("3 dup *"), Ok(9));
+    assert_eq!(evaluate_str::<i32>("3 4 drop"), Ok(3));
+    assert_eq!(evaluate_str::<i32>("3 4 swap -"), Ok(1));
+    assert_eq!(evaluate_str::<i32>("3 4 over - -"), Ok(2));
+    assert_eq!(evaluate_str::<i32>("1 2 3 rot - *"), Ok(4));
+}
+
+#[test]
+fn test_stack_words_underflow() {
+    assert_eq!(
+        evaluate_str::<i32>("1 2 rot"),
+        Err(CalculatorError {
+            index: 2,
+            kind: ErrorKind::StackUnderflow,
+            stack: vec![1, 2]
+        })
+    );
+}
+
+#[test]
+fn test_failed_input_leaves_the_stack_untouched() {
+    assert_eq!(
+        evaluate_str::<i32>("1 5 0 %"),
+        Err(CalculatorError {
+            index: 3,
+            kind: ErrorKind::DivisionByZero,
+            stack: vec![1, 5, 0]
+        })
+    );
+}
+
+#[test]
+fn test_i64_does_not_overflow_like_i32() {
+    assert_eq!(evaluate_str::<i64>("2147483647 1 +"), Ok(2147483648));
+}
+
+#[test]
+fn test_floats() {
+    assert_eq!(evaluate_str::<f64>("1 4 /"), Ok(0.25));
+    assert_eq!(evaluate_str::<f64>("2 0.5 ^"), Ok(2f64.sqrt()));
+    assert_eq!(
+        evaluate_str::<f64>("1 0 /").unwrap_err().kind,
+        ErrorKind::DivisionByZero
+    );
+    assert_eq!(
+        evaluate_str::<f64>("1e308 10 *").unwrap_err().kind,
+        ErrorKind::Overflow
+    );
+}
+
+#[test]
+fn test_rationals_are_exact() {
+    assert_eq!(
+        evaluate_str::<Rational>("1 3 / 1 6 / +"),
+        Ok("1/2".parse().unwrap())
+    );
+    assert_eq!(
+        evaluate_str::<Rational>("2/3 -2 ^").map(|result| result.to_string()),
+        Ok("9/4".to_string())
+    );
+}
+
+#[test]
+fn test_rationals_do_not_overflow() {
+    let result = evaluate_str::<Rational>("2 100 ^").unwrap();
+
+    assert_eq!(result.to_string(), "1267650600228229401496703205376");
+}
+
+#[test]
+fn test_rational_power_needs_an_integer_exponent() {
+    assert_eq!(
+        evaluate_str::<Rational>("2 1/2 ^").unwrap_err().kind,
+        ErrorKind::InvalidExponent
+    );
+}
+
+#[test]
+fn test_float_power_without_a_real_result() {
+    assert_eq!(
+        evaluate_str::<f64>("-8 0.5 ^").unwrap_err().kind,
+        ErrorKind::InvalidExponent
+    );
+    assert_eq!(
+        evaluate_str::<f64>("inf inf -").unwrap_err().kind,
+        ErrorKind::Overflow
+    );
+}
+
+#[test]
+fn test_rational_power_overflow() {
+    assert_eq!(
+        evaluate_str::<Rational>("2 2147483647 ^").unwrap_err().kind,
+        ErrorKind::Overflow
+    );
+    assert_eq!(
+        evaluate_str::<Rational>("1/2 -2147483647 ^").unwrap_err().kind,
+        ErrorKind::Overflow
+    );
+    assert_eq!(
+        evaluate_str::<Rational>("-1 2147483647 ^"),
+        Ok("-1".parse().unwrap())
+    );
+    assert_eq!(
+        evaluate_str::<Rational>("0 2147483647 ^"),
+        Ok("0".parse().unwrap())
+    );
+}
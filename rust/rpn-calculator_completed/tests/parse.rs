@@ -0,0 +1,140 @@
+use rpn_calculator::*;
+
+#[test]
+fn test_tokenize_rpn() {
+    assert_eq!(
+        tokenize("3 4 + 2 *"),
+        Some(vec![
+            CalculatorInput::Value(3),
+            CalculatorInput::Value(4),
+            CalculatorInput::Add,
+            CalculatorInput::Value(2),
+            CalculatorInput::Multiply,
+        ])
+    );
+}
+
+#[test]
+fn test_tokenize_negative_values() {
+    assert_eq!(
+        tokenize("-3 4 -"),
+        Some(vec![
+            CalculatorInput::Value(-3),
+            CalculatorInput::Value(4),
+            CalculatorInput::Subtract,
+        ])
+    );
+}
+
+#[test]
+fn test_tokenize_unknown_token() {
+    assert_eq!(tokenize::<i32>("3 4 ?"), None);
+}
+
+#[test]
+fn test_tokenized_input_can_be_evaluated() {
+    assert_eq!(evaluate(&tokenize("4 8 + 7 5 - /").unwrap()), Ok(6));
+}
+
+#[test]
+fn test_infix_parentheses() {
+    assert_eq!(
+        infix_to_rpn("(3 + 4) * 2"),
+        Some(vec![
+            CalculatorInput::Value(3),
+            CalculatorInput::Value(4),
+            CalculatorInput::Add,
+            CalculatorInput::Value(2),
+            CalculatorInput::Multiply,
+        ])
+    );
+}
+
+#[test]
+fn test_infix_precedence() {
+    assert_eq!(evaluate_infix("3 + 4 * 2"), Some(11));
+    assert_eq!(evaluate_infix("3 * 4 + 2"), Some(14));
+}
+
+#[test]
+fn test_infix_left_associativity() {
+    assert_eq!(evaluate_infix("10 - 4 - 3"), Some(3));
+    assert_eq!(evaluate_infix("100 / 10 / 5"), Some(2));
+}
+
+#[test]
+fn test_infix_without_spaces() {
+    assert_eq!(evaluate_infix("(4+8)/(7-5)"), Some(6));
+}
+
+#[test]
+fn test_infix_nested_parentheses() {
+    assert_eq!(evaluate_infix("2 * ((1 + 2) * (3 + 4))"), Some(42));
+}
+
+#[test]
+fn test_infix_negation() {
+    assert_eq!(evaluate_infix("-3 + 4"), Some(1));
+    assert_eq!(evaluate_infix("2 - -3"), Some(5));
+    assert_eq!(evaluate_infix("-(1 + 2) * 3"), Some(-9));
+}
+
+#[test]
+fn test_infix_malformed_expressions() {
+    assert_eq!(infix_to_rpn::<i32>(""), None);
+    assert_eq!(infix_to_rpn::<i32>("3 4"), None);
+    assert_eq!(infix_to_rpn::<i32>("3 +"), None);
+    assert_eq!(infix_to_rpn::<i32>("(3 + 4"), None);
+    assert_eq!(infix_to_rpn::<i32>("3 + 4)"), None);
+    assert_eq!(infix_to_rpn::<i32>("()"), None);
+    assert_eq!(infix_to_rpn::<i32>("3 & 4"), None);
+}
+
+#[test]
+fn test_tokenize_words() {
+    assert_eq!(
+        tokenize("1 2 % ^ neg abs min max dup drop swap over rot mod pow"),
+        Some(vec![
+            CalculatorInput::Value(1),
+            CalculatorInput::Value(2),
+            CalculatorInput::Modulo,
+            CalculatorInput::Power,
+            CalculatorInput::Negate,
+            CalculatorInput::Abs,
+            CalculatorInput::Min,
+            CalculatorInput::Max,
+            CalculatorInput::Dup,
+            CalculatorInput::Drop,
+            CalculatorInput::Swap,
+            CalculatorInput::Over,
+            CalculatorInput::Rot,
+            CalculatorInput::Modulo,
+            CalculatorInput::Power,
+        ])
+    );
+}
+
+#[test]
+fn test_infix_power_is_right_associative() {
+    assert_eq!(evaluate_infix("2 ^ 3 ^ 2"), Some(512));
+    assert_eq!(evaluate_infix("-2 ^ 2"), Some(-4));
+    assert_eq!(evaluate_infix("2 * 3 ^ 2"), Some(18));
+}
+
+#[test]
+fn test_infix_modulo() {
+    assert_eq!(evaluate_infix("17 % 5 + 1"), Some(3));
+}
+
+#[test]
+fn test_infix_with_floats() {
+    assert_eq!(evaluate_infix("1.5 * (2 + 0.5)"), Some(3.75));
+}
+
+#[test]
+fn test_infix_with_rationals() {
+    assert_eq!(
+        evaluate_infix::<Rational>("1 / 3 + 1 / 6"),
+        Some("1/2".parse().unwrap())
+    );
+}
@@ -0,0 +1,111 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn rpn(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rpn"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_stack_is_kept_between_lines() {
+    let output = rpn(&[], "3 4\n+\n2 *\n");
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "3 4\n7\n14\n");
+}
+
+#[test]
+fn test_words_are_kept_between_lines() {
+    let output = rpn(&[], ": square dup * ;\n5 square\n");
+
+    assert_eq!(stdout(&output), "\n25\n");
+}
+
+#[test]
+fn test_undo() {
+    let output = rpn(&[], "1 2\n+\nundo\nundo\nundo\n");
+
+    assert_eq!(stdout(&output), "1 2\n3\n1 2\n\nnothing to undo\n\n");
+}
+
+#[test]
+fn test_history() {
+    let output = rpn(&[], "1\n2 +\nhistory\n");
+
+    assert_eq!(stdout(&output), "1\n3\n   1  1\n   2  2 +\n");
+}
+
+#[test]
+fn test_quit() {
+    let output = rpn(&[], "1\nquit\n2\n");
+
+    assert_eq!(stdout(&output), "1\n");
+}
+
+#[test]
+fn test_first_error_stops_non_interactive_mode() {
+    let output = rpn(&[], "1 0\n/\n2\n");
+
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), "1 0\n");
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "rpn: line 2: division by zero at input 0 (stack: [1, 0])\n"
+    );
+}
+
+#[test]
+fn test_unparseable_line() {
+    let output = rpn(&[], "1 ?\n");
+
+    assert!(!output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "rpn: line 1: cannot parse the line\n"
+    );
+}
+
+#[test]
+fn test_other_numbers() {
+    assert_eq!(stdout(&rpn(&["--numbers", "f64"], "1 4 /\n")), "0.25\n");
+    assert_eq!(
+        stdout(&rpn(&["--numbers", "rational"], "1 3 / 1 6 / +\n")),
+        "1/2\n"
+    );
+}
+
+#[test]
+fn test_reads_a_file() {
+    let path = std::env::temp_dir().join(format!("rpn-test-{}.txt", std::process::id()));
+    std::fs::write(&path, "2 3\n^\n").unwrap();
+
+    let output = rpn(&[path.to_str().unwrap()], "");
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(stdout(&output), "2 3\n8\n");
+}
+
+#[test]
+fn test_usage_error() {
+    let output = rpn(&["--numbers", "u8"], "");
+
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -13,67 +13,140 @@ fn calculator_input(s: &str) -> Vec<CalculatorInput> {
 }
 
 #[test]
-fn test_empty_input_returns_none() {
+fn test_empty_input_returns_an_error() {
     let input = calculator_input("");
-    assert_eq!(evaluate(&input), None);
+    assert_eq!(
+        evaluate(&input),
+        Err(CalculatorError {
+            index: 0,
+            kind: ErrorKind::NoSingleResult,
+            stack: vec![]
+        })
+    );
 }
 
 #[test]
 fn test_simple_value() {
     let input = calculator_input("10");
-    assert_eq!(evaluate(&input), Some(10));
+    assert_eq!(evaluate(&input), Ok(10));
 }
 
 #[test]
 fn test_simple_addition() {
     let input = calculator_input("2 2 +");
-    assert_eq!(evaluate(&input), Some(4));
+    assert_eq!(evaluate(&input), Ok(4));
 }
 
 #[test]
 fn test_simple_subtraction() {
     let input = calculator_input("7 11 -");
-    assert_eq!(evaluate(&input), Some(-4));
+    assert_eq!(evaluate(&input), Ok(-4));
 }
 
 #[test]
 fn test_simple_multiplication() {
     let input = calculator_input("6 9 *");
-    assert_eq!(evaluate(&input), Some(54));
+    assert_eq!(evaluate(&input), Ok(54));
 }
 
 #[test]
 fn test_simple_division() {
     let input = calculator_input("57 19 /");
-    assert_eq!(evaluate(&input), Some(3));
+    assert_eq!(evaluate(&input), Ok(3));
 }
 
 #[test]
 fn test_complex_operation() {
     let input = calculator_input("4 8 + 7 5 - /");
-    assert_eq!(evaluate(&input), Some(6));
+    assert_eq!(evaluate(&input), Ok(6));
 }
 
 #[test]
-fn test_too_few_operands_returns_none() {
+fn test_too_few_operands_returns_an_error() {
     let input = calculator_input("2 +");
-    assert_eq!(evaluate(&input), None);
+    assert_eq!(
+        evaluate(&input),
+        Err(CalculatorError {
+            index: 1,
+            kind: ErrorKind::StackUnderflow,
+            stack: vec![2]
+        })
+    );
 }
 
 #[test]
-fn test_too_many_operands_returns_none() {
+fn test_too_many_operands_returns_an_error() {
     let input = calculator_input("2 2");
-    assert_eq!(evaluate(&input), None);
+    assert_eq!(
+        evaluate(&input),
+        Err(CalculatorError {
+            index: 2,
+            kind: ErrorKind::NoSingleResult,
+            stack: vec![2, 2]
+        })
+    );
 }
 
 #[test]
-fn test_zero_operands_returns_none() {
+fn test_zero_operands_returns_an_error() {
     let input = calculator_input("+");
-    assert_eq!(evaluate(&input), None);
+    assert_eq!(
+        evaluate(&input),
+        Err(CalculatorError {
+            index: 0,
+            kind: ErrorKind::StackUnderflow,
+            stack: vec![]
+        })
+    );
 }
 
 #[test]
-fn test_intermediate_error_returns_none() {
+fn test_intermediate_error_returns_an_error() {
     let input = calculator_input("+ 2 2 *");
-    assert_eq!(evaluate(&input), None);
+    assert_eq!(
+        evaluate(&input),
+        Err(CalculatorError {
+            index: 0,
+            kind: ErrorKind::StackUnderflow,
+            stack: vec![]
+        })
+    );
+}
+
+#[test]
+fn test_division_by_zero_returns_an_error() {
+    let input = calculator_input("1 2 3 - 1 + /");
+    assert_eq!(
+        evaluate(&input),
+        Err(CalculatorError {
+            index: 6,
+            kind: ErrorKind::DivisionByZero,
+            stack: vec![1, 0]
+        })
+    );
+}
+
+#[test]
+fn test_overflow_returns_an_error() {
+    let input = calculator_input("2147483647 1 +");
+    assert_eq!(
+        evaluate(&input),
+        Err(CalculatorError {
+            index: 2,
+            kind: ErrorKind::Overflow,
+            stack: vec![2147483647, 1]
+        })
+    );
+}
+
+#[test]
+fn test_dividing_the_lowest_value_by_minus_one_overflows() {
+    let input = calculator_input("-2147483648 -1 /");
+    assert_eq!(evaluate(&input).unwrap_err().kind, ErrorKind::Overflow);
+}
+
+#[test]
+fn test_error_message() {
+    let error = evaluate(&calculator_input("3 +")).unwrap_err();
+    assert_eq!(error.to_string(), "not enough operands at input 1 (stack: [3])");
 }
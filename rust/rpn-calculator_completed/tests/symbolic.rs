@@ -0,0 +1,147 @@
+use rpn_calculator::*;
+
+fn symbolic_str(s: &str) -> Expression {
+    symbolic(&tokenize(s).unwrap()).unwrap()
+}
+
+fn formula(s: &str) -> String {
+    symbolic_str(s).to_string()
+}
+
+#[test]
+fn test_expression_tree() {
+    assert_eq!(
+        symbolic_str("@x 2 *"),
+        Expression::Binary(
+            BinaryOperator::Multiply,
+            Box::new(Expression::Variable("x".to_string())),
+            Box::new(Expression::Value(2))
+        )
+    );
+}
+
+#[test]
+fn test_minimal_parentheses() {
+    assert_eq!(formula("@a @b + @c *"), "(a + b) * c");
+    assert_eq!(formula("@a @b @c * +"), "a + b * c");
+    assert_eq!(formula("@a @b - @c -"), "a - b - c");
+    assert_eq!(formula("@a @b @c - -"), "a - (b - c)");
+    assert_eq!(formula("@a @b @c - +"), "a + b - c");
+    assert_eq!(formula("@a @b @c / /"), "a / (b / c)");
+    assert_eq!(formula("@a @b @c * *"), "a * b * c");
+    assert_eq!(formula("@a @b @c ^ ^"), "a^b^c");
+    assert_eq!(formula("@a @b ^ @c ^"), "(a^b)^c");
+    assert_eq!(formula("@a @b + neg"), "-(a + b)");
+    assert_eq!(formula("@a neg 2 ^"), "(-a)^2");
+    assert_eq!(formula("@a 2 ^ neg"), "-a^2");
+    assert_eq!(formula("@a -3 *"), "a * -3");
+    assert_eq!(formula("-3 2 ^"), "(-3)^2");
+}
+
+#[test]
+fn test_functions() {
+    assert_eq!(formula("@a @b - abs @c min"), "min(abs(a - b), c)");
+}
+
+#[test]
+fn test_fractions_are_parenthesized_like_divisions() {
+    let expression: Expression<Rational> = symbolic(&tokenize("@x 1/2 * 1/2 @x * /").unwrap()).unwrap();
+
+    assert_eq!(expression.to_string(), "x * (1/2) / (1/2 * x)");
+}
+
+#[test]
+fn test_printed_formula_evaluates_like_the_inputs() {
+    for s in ["1 2 3 - -", "2 3 2 ^ ^", "2 neg 2 ^", "7 2 3 * %", "10 2 5 * -"]
+    {
+        let inputs: Vec<CalculatorInput> = tokenize(s).unwrap();
+        let infix = symbolic(&inputs).unwrap().to_string();
+        assert_eq!(evaluate_infix(&infix), evaluate(&inputs).ok(), "{}", infix);
+    }
+}
+
+#[test]
+fn test_variables_and_words() {
+    assert_eq!(formula(": square dup * ; @x 1 + square"), "(x + 1) * (x + 1)");
+    assert_eq!(formula("@x 1 + !y @y @y *"), "(x + 1) * (x + 1)");
+    assert_eq!(formula("@a @b swap -"), "b - a");
+}
+
+#[test]
+fn test_errors() {
+    assert_eq!(
+        symbolic(&tokenize::<i32>("@x 1 <").unwrap()),
+        Err(CompileError {
+            index: 2,
+            kind: ErrorKind::NotSymbolic
+        })
+    );
+    assert_eq!(
+        symbolic(&tokenize::<i32>("@x +").unwrap()).unwrap_err().kind,
+        ErrorKind::StackUnderflow
+    );
+    assert_eq!(
+        symbolic(&tokenize::<i32>("@x @y").unwrap()).unwrap_err().kind,
+        ErrorKind::NoSingleResult
+    );
+}
+
+#[test]
+fn test_constant_folding() {
+    assert_eq!(symbolic_str("2 3 * 4 +").simplify(), Expression::Value(10));
+    assert_eq!(symbolic_str("@x 2 3 * +").simplify().to_string(), "x + 6");
+    assert_eq!(symbolic_str("5 neg neg").simplify(), Expression::Value(5));
+    assert_eq!(symbolic_str("1 0 / @x +").simplify().to_string(), "1 / 0 + x");
+}
+
+#[test]
+fn test_algebraic_simplification() {
+    assert_eq!(symbolic_str("@x 1 *").simplify().to_string(), "x");
+    assert_eq!(symbolic_str("0 @x +").simplify().to_string(), "x");
+    assert_eq!(symbolic_str("@x 0 *").simplify(), Expression::Value(0));
+    assert_eq!(symbolic_str("0 @x -").simplify().to_string(), "-x");
+    assert_eq!(symbolic_str("@x @y + @x @y + -").simplify(), Expression::Value(0));
+    assert_eq!(symbolic_str("@x 1 ^ 1 /").simplify().to_string(), "x");
+    assert_eq!(symbolic_str("@x 0 ^").simplify(), Expression::Value(1));
+    assert_eq!(symbolic_str("@x neg neg 3 2 - *").simplify().to_string(), "x");
+}
+
+#[test]
+fn test_simplification_keeps_the_operations_which_can_fail() {
+    assert_eq!(symbolic_str("1 0 / 0 *").simplify().to_string(), "1 / 0 * 0");
+    assert_eq!(symbolic_str("0 1 @x / *").simplify().to_string(), "0 * (1 / x)");
+    assert_eq!(symbolic_str("@x 0 % 0 ^").simplify().to_string(), "(x % 0)^0");
+    assert_eq!(symbolic_str("1 @x / dup -").simplify().to_string(), "1 / x - 1 / x");
+    assert_eq!(symbolic_str("@x 2 + 0 *").simplify(), Expression::Value(0));
+}
+
+#[test]
+fn test_derivatives() {
+    let derivative = |s: &str, variable: &str| symbolic_str(s).derivative(variable).unwrap().to_string();
+
+    assert_eq!(derivative("@x 3 * 5 +", "x"), "3");
+    assert_eq!(derivative("@x 3 ^", "x"), "3 * x^2");
+    assert_eq!(derivative("@x @y *", "y"), "x");
+    assert_eq!(derivative("@x @x *", "x"), "x + x");
+    assert_eq!(derivative("1 @x /", "x"), "-1 / (x * x)");
+    assert_eq!(derivative("@x 2 * 1 + 2 ^", "x"), "2 * (x * 2 + 1) * 2");
+    assert_eq!(derivative("@x neg", "x"), "-1");
+    assert_eq!(derivative("@y abs", "x"), "0");
+}
+
+#[test]
+fn test_derivative_checked_against_the_calculator() {
+    let expression: Expression<f64> = symbolic(&tokenize("@x @x * @x 3 ^ +").unwrap()).unwrap();
+    let derivative = expression.derivative("x").unwrap();
+    let program = compile(&derivative.to_rpn()).unwrap();
+
+    // 2x + 3x^2
+    assert_eq!(program.run(&[2.0]), Ok(16.0));
+}
+
+#[test]
+fn test_not_differentiable() {
+    assert_eq!(symbolic_str("@x abs").derivative("x"), None);
+    assert_eq!(symbolic_str("2 @x ^").derivative("x"), None);
+    assert_eq!(symbolic_str("@x 3 %").derivative("x"), None);
+}
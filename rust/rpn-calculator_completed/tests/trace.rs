@@ -0,0 +1,151 @@
+use rpn_calculator::*;
+
+fn trace_str(s: &str) -> Trace {
+    trace(&tokenize(s).unwrap())
+}
+
+#[test]
+fn test_steps() {
+    let trace = trace_str("3 4 +");
+
+    assert_eq!(trace.result, Ok(7));
+    assert_eq!(
+        trace.steps,
+        vec![
+            Step {
+                index: 0,
+                depth: 0,
+                input: CalculatorInput::Value(3),
+                before: vec![],
+                after: Ok(vec![3])
+            },
+            Step {
+                index: 1,
+                depth: 0,
+                input: CalculatorInput::Value(4),
+                before: vec![3],
+                after: Ok(vec![3, 4])
+            },
+            Step {
+                index: 2,
+                depth: 0,
+                input: CalculatorInput::Add,
+                before: vec![3, 4],
+                after: Ok(vec![7])
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_failing_step() {
+    let trace = trace_str("1 0 / 2");
+
+    assert_eq!(trace.steps.len(), 3);
+    assert_eq!(trace.steps[2].after, Err(ErrorKind::DivisionByZero));
+    assert_eq!(trace.result.unwrap_err().index, 2);
+}
+
+#[test]
+fn test_steps_in_words_and_loops() {
+    let trace = trace_str(": square dup * ; 0 3 0 do i square + loop");
+    let steps: Vec<(usize, usize, String)> = trace
+        .steps
+        .iter()
+        .map(|step| (step.index, step.depth, step.input.to_string()))
+        .collect();
+
+    assert_eq!(trace.result, Ok(5));
+    assert_eq!(steps.len(), 1 + 3 + 3 * 4);
+    assert_eq!(steps[0], (0, 0, ": square dup * ;".to_string()));
+    assert_eq!(steps[4], (4, 1, "i".to_string()));
+    assert_eq!(steps[5], (4, 2, "dup".to_string()));
+    assert_eq!(steps[7], (4, 1, "+".to_string()));
+}
+
+#[test]
+fn test_table() {
+    assert_eq!(
+        trace_str("10 2 - : half 2 / ; half").to_string(),
+        "\
+step  input         before  after
+   1  10                    10
+   2  2             10      10 2
+   3  -             10 2    8
+   4  : half 2 / ;  8       8
+   5    2           8       8 2
+   6    /           8 2     4
+result: 4"
+    );
+    assert_eq!(
+        trace_str("1 0 /").to_string(),
+        "\
+step  input  before  after
+   1  1              1
+   2  0      1       1 0
+   3  /      1 0     error: division by zero
+error: division by zero at input 2 (stack: [1, 0])"
+    );
+}
+
+#[test]
+fn test_inputs_print_as_tokens() {
+    let s = ": f dup 0 < if neg else 1 + then ; 3 0 do @x i + !x loop begin 1 until 2 swap";
+
+    assert_eq!(
+        tokenize::<i32>(s)
+            .unwrap()
+            .iter()
+            .map(|input| input.to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+        s
+    );
+}
+
+#[test]
+fn test_breakpoints() {
+    let inputs = tokenize("1 2 + 3 * 4 -").unwrap();
+    let mut seen = vec![];
+    let trace = debug(&inputs, &[2, 4], |step| {
+        seen.push((step.index, step.after.clone().unwrap()));
+        Command::Continue
+    });
+
+    assert_eq!(trace.result, Ok(5));
+    assert_eq!(trace.steps.len(), 7);
+    assert_eq!(seen, vec![(2, vec![3]), (4, vec![9])]);
+}
+
+#[test]
+fn test_single_stepping() {
+    let inputs = tokenize("1 2 + 3 * 4 -").unwrap();
+    let mut seen = vec![];
+    let trace = debug(&inputs, &[3], |step| {
+        seen.push(step.index);
+        match step.index
+        {
+            5 => Command::Continue,
+            _ => Command::Step,
+        }
+    });
+
+    assert_eq!(trace.result, Ok(5));
+    assert_eq!(seen, vec![3, 4, 5]);
+}
+
+#[test]
+fn test_abort() {
+    let inputs = tokenize("1 2 + 3 *").unwrap();
+    let trace = debug(&inputs, &[2], |_| Command::Abort);
+
+    assert_eq!(
+        trace.result,
+        Err(CalculatorError {
+            index: 2,
+            kind: ErrorKind::Interrupted,
+            stack: vec![1, 2]
+        })
+    );
+    assert_eq!(trace.steps.len(), 3);
+}
@@ -0,0 +1,220 @@
+use rpn_calculator::*;
+
+fn compile_str(s: &str) -> Result<Program, CompileError> {
+    compile(&tokenize(s).unwrap())
+}
+
+#[test]
+fn test_compiled_program_matches_evaluate() {
+    for s in [
+        "3 4 + 2 *",
+        "2 3 4 rot - *",
+        "1 2 over swap drop -",
+        "7 neg abs 3 max 2 min",
+        "2 10 ^ 1000 %",
+        ": square dup * ; 3 square square",
+        "3 4 < 5 5 = + 2 7 >= -",
+        "-7 dup 0 < if neg then",
+        "0 5 0 do i + loop",
+        "0 3 0 do 3 0 do i + loop loop",
+        "1 begin 2 * dup 100 > until",
+        ": fact 1 swap 1 + 1 do i * loop ; 10 fact",
+    ]
+    {
+        let inputs: Vec<CalculatorInput> = tokenize(s).unwrap();
+        assert_eq!(compile(&inputs).unwrap().run(&[]), evaluate(&inputs), "{}", s);
+    }
+}
+
+#[test]
+fn test_bytecode() {
+    assert_eq!(
+        compile_str("@x 2 * 1 +").unwrap().bytecode(),
+        &[
+            Instruction::Load(0),
+            Instruction::Constant(0),
+            Instruction::Multiply,
+            Instruction::Constant(1),
+            Instruction::Add,
+        ]
+    );
+}
+
+#[test]
+fn test_parameters() {
+    let program = compile_str("@x @y - @x *").unwrap();
+
+    assert_eq!(program.parameters(), &["x".to_string(), "y".to_string()]);
+    assert_eq!(program.run(&[5, 3]), Ok(10));
+    assert_eq!(program.run(&[2, 7]), Ok(-10));
+}
+
+#[test]
+fn test_stored_variables_are_not_parameters() {
+    let program = compile_str("@x dup * !square @square @y + @square *").unwrap();
+
+    assert_eq!(program.parameters(), &["x".to_string(), "y".to_string()]);
+    assert_eq!(program.run(&[3, 1]), Ok(90));
+}
+
+#[test]
+fn test_parameters_can_be_overwritten() {
+    assert_eq!(compile_str("@n 1 + !n @n @n *").unwrap().run(&[4]), Ok(25));
+}
+
+#[test]
+fn test_words_are_inlined() {
+    let program = compile_str(": twice 2 * ; @x twice twice").unwrap();
+
+    assert_eq!(program.bytecode().len(), 5);
+    assert_eq!(program.run(&[3]), Ok(12));
+}
+
+#[test]
+fn test_stack_underflow_is_found_by_the_compiler() {
+    assert_eq!(
+        compile_str("1 2 + +"),
+        Err(CompileError {
+            index: 3,
+            kind: ErrorKind::StackUnderflow
+        })
+    );
+    assert_eq!(
+        compile_str(": plus + ; 1 plus").unwrap_err(),
+        CompileError {
+            index: 2,
+            kind: ErrorKind::StackUnderflow
+        }
+    );
+}
+
+#[test]
+fn test_result_is_checked_by_the_compiler() {
+    assert_eq!(
+        compile_str("1 2"),
+        Err(CompileError {
+            index: 2,
+            kind: ErrorKind::NoSingleResult
+        })
+    );
+    assert_eq!(compile_str("").unwrap_err().kind, ErrorKind::NoSingleResult);
+}
+
+#[test]
+fn test_unknown_word() {
+    assert_eq!(
+        compile_str("1 foo"),
+        Err(CompileError {
+            index: 1,
+            kind: ErrorKind::UnknownWord
+        })
+    );
+}
+
+#[test]
+fn test_runtime_errors() {
+    let program = compile_str("1 @x / 2 +").unwrap();
+
+    assert_eq!(program.run(&[1]), Ok(3));
+    assert_eq!(
+        program.run(&[0]),
+        Err(CalculatorError {
+            index: 2,
+            kind: ErrorKind::DivisionByZero,
+            stack: vec![1, 0]
+        })
+    );
+}
+
+#[test]
+fn test_other_numbers() {
+    let program: Program<f64> = compile(&tokenize("@x @x * 2 /").unwrap()).unwrap();
+
+    assert_eq!(program.run(&[3.0]), Ok(4.5));
+}
+
+#[test]
+#[should_panic]
+fn test_wrong_amount_of_parameters() {
+    let _ = compile_str("@x @y +").unwrap().run(&[1]);
+}
+
+#[test]
+fn test_control_flow_with_parameters() {
+    let program = compile_str("@n 0 < if 0 else 1 @n 1 + 1 do i * loop then").unwrap();
+
+    assert_eq!(program.run(&[5]), Ok(120));
+    assert_eq!(program.run(&[0]), Ok(1));
+    assert_eq!(program.run(&[-3]), Ok(0));
+}
+
+#[test]
+fn test_unbalanced_control_flow_is_refused() {
+    assert_eq!(
+        compile_str("1 1 if 2 then"),
+        Err(CompileError {
+            index: 2,
+            kind: ErrorKind::UnbalancedStack
+        })
+    );
+    assert_eq!(
+        compile_str("0 3 0 do i loop").unwrap_err().kind,
+        ErrorKind::UnbalancedStack
+    );
+    assert_eq!(
+        compile_str("1 begin dup 0 until").unwrap_err().kind,
+        ErrorKind::UnbalancedStack
+    );
+}
+
+#[test]
+fn test_loop_index_outside_of_a_loop_is_refused() {
+    assert_eq!(
+        compile_str("i"),
+        Err(CompileError {
+            index: 0,
+            kind: ErrorKind::NoLoop
+        })
+    );
+}
+
+#[test]
+fn test_local_stored_in_a_branch() {
+    let program = compile_str("@x if 1 !y then @y").unwrap();
+
+    assert_eq!(program.run(&[1]), Ok(1));
+    assert_eq!(
+        program.run(&[0]),
+        Err(CalculatorError {
+            index: 2,
+            kind: ErrorKind::UnknownVariable,
+            stack: vec![]
+        })
+    );
+}
+
+#[test]
+fn test_runaway_program_hits_the_step_limit() {
+    let mut program = compile_str("0 begin 1 + dup 0 < until").unwrap();
+    program.set_step_limit(100);
+
+    assert_eq!(program.run(&[]).unwrap_err().kind, ErrorKind::StepLimitExceeded);
+}
+
+#[test]
+fn test_program_too_large() {
+    let constants = format!("0{}", " 1 +".repeat(70_000));
+    assert_eq!(
+        compile_str(&constants).unwrap_err().kind,
+        ErrorKind::ProgramTooLarge
+    );
+
+    let branch = format!("1 1 if{} then", " dup drop".repeat(40_000));
+    assert_eq!(
+        compile_str(&branch).unwrap_err(),
+        CompileError {
+            index: 2,
+            kind: ErrorKind::ProgramTooLarge,
+        }
+    );
+}
@@ -0,0 +1,148 @@
+use rpn_calculator::*;
+
+fn evaluate_str(s: &str) -> Result<i32, CalculatorError> {
+    evaluate(&tokenize(s).unwrap())
+}
+
+#[test]
+fn test_tokenize_definition() {
+    assert_eq!(
+        tokenize(": square dup * ; 3 square"),
+        Some(vec![
+            CalculatorInput::Define(
+                "square".to_string(),
+                vec![CalculatorInput::Dup, CalculatorInput::Multiply]
+            ),
+            CalculatorInput::Value(3),
+            CalculatorInput::Word("square".to_string()),
+        ])
+    );
+}
+
+#[test]
+fn test_tokenize_variables() {
+    assert_eq!(
+        tokenize("5 !x @x"),
+        Some(vec![
+            CalculatorInput::Value(5),
+            CalculatorInput::Store("x".to_string()),
+            CalculatorInput::Fetch("x".to_string()),
+        ])
+    );
+}
+
+#[test]
+fn test_tokenize_malformed_definitions() {
+    assert_eq!(tokenize::<i32>(": square dup *"), None);
+    assert_eq!(tokenize::<i32>(": 3 dup * ;"), None);
+    assert_eq!(tokenize::<i32>(": dup 2 ;"), None);
+    assert_eq!(tokenize::<i32>(": a : b 1 ; ;"), None);
+    assert_eq!(tokenize::<i32>("1 ;"), None);
+}
+
+#[test]
+fn test_user_defined_word() {
+    assert_eq!(evaluate_str(": square dup * ; 7 square"), Ok(49));
+}
+
+#[test]
+fn test_words_can_use_other_words() {
+    assert_eq!(
+        evaluate_str(": square dup * ; : fourth square square ; 2 fourth"),
+        Ok(16)
+    );
+}
+
+#[test]
+fn test_redefinition_shadows_the_earlier_definition() {
+    assert_eq!(evaluate_str(": one 1 ; : one 2 ; one"), Ok(2));
+}
+
+#[test]
+fn test_redefinition_does_not_change_compiled_words() {
+    assert_eq!(
+        evaluate_str(": foo 5 ; : bar foo ; : foo 6 ; bar foo +"),
+        Ok(11)
+    );
+}
+
+#[test]
+fn test_word_can_use_its_earlier_definition() {
+    assert_eq!(evaluate_str(": foo 10 ; : foo foo 1 + ; foo"), Ok(11));
+}
+
+#[test]
+fn test_unknown_word() {
+    assert_eq!(
+        evaluate_str("1 foo"),
+        Err(CalculatorError {
+            index: 1,
+            kind: ErrorKind::UnknownWord,
+            stack: vec![1]
+        })
+    );
+    assert_eq!(
+        evaluate_str(": bar foo ; 1").unwrap_err().kind,
+        ErrorKind::UnknownWord
+    );
+}
+
+#[test]
+fn test_failing_word_leaves_the_stack_untouched() {
+    assert_eq!(
+        evaluate_str(": broken 1 + 0 / ; 4 broken"),
+        Err(CalculatorError {
+            index: 2,
+            kind: ErrorKind::DivisionByZero,
+            stack: vec![4]
+        })
+    );
+}
+
+#[test]
+fn test_variables() {
+    assert_eq!(evaluate_str("6 !x 7 !y @x @y *"), Ok(42));
+    assert_eq!(evaluate_str("1 !x 2 !x @x"), Ok(2));
+}
+
+#[test]
+fn test_variables_in_words() {
+    assert_eq!(
+        evaluate_str("0 !count : tick @count 1 + !count ; tick tick tick @count"),
+        Ok(3)
+    );
+}
+
+#[test]
+fn test_unknown_variable() {
+    assert_eq!(
+        evaluate_str("@x"),
+        Err(CalculatorError {
+            index: 0,
+            kind: ErrorKind::UnknownVariable,
+            stack: vec![]
+        })
+    );
+}
+
+#[test]
+fn test_store_needs_a_value() {
+    assert_eq!(
+        evaluate_str("!x 1").unwrap_err().kind,
+        ErrorKind::StackUnderflow
+    );
+}
+
+#[test]
+fn test_words_defined_from_other_words_do_not_grow() {
+    let mut program = ": w0 1 ;".to_string();
+    for i in 1..64 {
+        program.push_str(&format!(" : w{} w{} w{} + ;", i, i - 1, i - 1));
+    }
+
+    assert_eq!(evaluate_str(&format!("{} w10", program)), Ok(1024));
+    assert_eq!(
+        evaluate_str(&format!("{} w63", program)).unwrap_err().kind,
+        ErrorKind::StepLimitExceeded
+    );
+}